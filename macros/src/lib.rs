@@ -0,0 +1,307 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Procedural macros for `const-cstr`.
+//!
+//! Not intended to be used directly; enable the corresponding features of `const-cstr`
+//! instead, which re-exports these macros next to the items they refer to.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Expr, ExprLit, Fields, Ident, Lit, LitByteStr,
+    LitStr, Meta,
+};
+
+/// Derives `const_cstr::AsCStr` for an enum with unit variants.
+///
+/// Each variant is represented by its name, unless overridden with `#[cstr = "..."]`.
+#[proc_macro_derive(AsCStr, attributes(cstr))]
+pub fn derive_as_cstr(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_as_cstr(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_as_cstr(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants = variants(input, "AsCStr")?;
+    let variant_idents = variants.iter().map(|variant| &variant.ident);
+    let variant_names = variants.iter().map(|variant| &variant.name);
+
+    Ok(quote! {
+        impl #impl_generics ::const_cstr::AsCStr for #ident #ty_generics #where_clause {
+            fn as_cstr(&self) -> ::const_cstr::ConstCStr {
+                match *self {
+                    #(#ident::#variant_idents => ::const_cstr::const_cstr!(#variant_names),)*
+                }
+            }
+        }
+    })
+}
+
+/// Derives `const_cstr::FromCStr` for an enum with unit variants.
+///
+/// Each variant is matched by its name, unless overridden with `#[cstr = "..."]`.
+#[proc_macro_derive(FromCStr, attributes(cstr))]
+pub fn derive_from_cstr(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_from_cstr(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_from_cstr(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants = variants(input, "FromCStr")?;
+
+    for (i, variant) in variants.iter().enumerate() {
+        let name = variant.name.value();
+
+        if name.contains('\0') {
+            return Err(Error::new_spanned(
+                &variant.name,
+                "C string contains an interior NUL byte",
+            ));
+        }
+
+        if variants[..i].iter().any(|prev| prev.name.value() == name) {
+            return Err(Error::new_spanned(
+                &variant.name,
+                format!("`{}` matches more than one variant", name),
+            ));
+        }
+    }
+
+    let variant_idents = variants.iter().map(|variant| &variant.ident);
+    let variant_bytes = variants.iter().map(|variant| {
+        LitByteStr::new(
+            format!("{}\0", variant.name.value()).as_bytes(),
+            variant.name.span(),
+        )
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::const_cstr::FromCStr for #ident #ty_generics #where_clause {
+            fn from_cstr(cstr: &::std::ffi::CStr) -> ::std::option::Option<Self> {
+                match cstr.to_bytes_with_nul() {
+                    #(#variant_bytes => ::std::option::Option::Some(#ident::#variant_idents),)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        }
+    })
+}
+
+/// A unit variant of an enum and the C string it is represented by.
+struct Variant {
+    ident: Ident,
+    name: LitStr,
+}
+
+/// Expands to a `&'static CStr` holding the given string or byte string literal followed by
+/// a NUL byte, rejecting literals containing a NUL byte.
+#[proc_macro]
+pub fn cstr(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as Lit);
+
+    expand_cstr(&lit)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_cstr(lit: &Lit) -> syn::Result<TokenStream2> {
+    let mut bytes = match lit {
+        Lit::Str(lit) => lit.value().into_bytes(),
+        Lit::ByteStr(lit) => lit.value(),
+        lit => return Err(Error::new_spanned(lit, "expected a string or byte string literal")),
+    };
+
+    if let Some(index) = bytes.iter().position(|&byte| byte == 0) {
+        return Err(Error::new_spanned(
+            lit,
+            format!("C string contains an interior NUL byte at index {}", index),
+        ));
+    }
+
+    bytes.push(0);
+    let bytes = LitByteStr::new(&bytes, lit.span());
+
+    Ok(quote! {
+        // Safe because the literal ends with its only NUL byte.
+        unsafe { ::std::ffi::CStr::from_bytes_with_nul_unchecked(#bytes) }
+    })
+}
+
+/// Expands to a `const_cstr::ConstCBytes` holding the given string literal with its C escape
+/// sequences decoded, followed by a NUL byte.
+#[proc_macro]
+pub fn cstr_escaped(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+
+    expand_cstr_escaped(&lit)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_cstr_escaped(lit: &LitStr) -> syn::Result<TokenStream2> {
+    let mut bytes = unescape(&lit.value()).map_err(|msg| Error::new_spanned(lit, msg))?;
+
+    if let Some(index) = bytes.iter().position(|&byte| byte == 0) {
+        return Err(Error::new_spanned(
+            lit,
+            format!("C string contains an interior NUL byte at index {}", index),
+        ));
+    }
+
+    bytes.push(0);
+    let bytes = LitByteStr::new(&bytes, lit.span());
+
+    Ok(quote! {
+        // Safe because the literal ends with its only NUL byte.
+        unsafe { ::const_cstr::ConstCBytes::from_bytes_with_nul_unchecked(#bytes) }
+    })
+}
+
+/// Decodes the escape sequences of a C string literal, except that `\x` takes at most two
+/// hexadecimal digits, so that every escape is exactly one byte.
+fn unescape(s: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+
+        let escape = chars.next().ok_or("unterminated escape sequence at end of string")?;
+
+        let byte = match escape {
+            'a' => 0x07,
+            'b' => 0x08,
+            'f' => 0x0C,
+            'n' => b'\n',
+            'r' => b'\r',
+            't' => b'\t',
+            'v' => 0x0B,
+            '\\' | '\'' | '"' | '?' => escape as u8,
+            'x' => {
+                let mut value = None;
+
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(16)) {
+                        Some(digit) => {
+                            value = Some(value.unwrap_or(0) * 16 + digit);
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+
+                value.ok_or("`\\x` must be followed by a hexadecimal digit")? as u8
+            }
+            '0'..='7' => {
+                let mut value = escape.to_digit(8).unwrap();
+
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            chars.next();
+                        }
+                        None => break,
+                    }
+                }
+
+                if value > 0o377 {
+                    return Err(format!("octal escape `\\{:o}` does not fit in a byte", value));
+                }
+
+                value as u8
+            }
+            escape => return Err(format!("unknown escape sequence `\\{}`", escape)),
+        };
+
+        bytes.push(byte);
+    }
+
+    Ok(bytes)
+}
+
+/// Collects the variants of `input`, which must be an enum with only unit variants.
+fn variants(input: &DeriveInput, derive: &str) -> syn::Result<Vec<Variant>> {
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                format!("`{}` can only be derived for enums", derive),
+            ))
+        }
+    };
+
+    data.variants
+        .iter()
+        .map(|variant| {
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(Error::new_spanned(
+                    variant,
+                    format!("`{}` can only be derived for enums with unit variants", derive),
+                ));
+            }
+
+            let name = match cstr_attr(&variant.attrs)? {
+                Some(name) => name,
+                None => LitStr::new(&variant.ident.unraw().to_string(), variant.ident.span()),
+            };
+
+            Ok(Variant {
+                ident: variant.ident.clone(),
+                name,
+            })
+        })
+        .collect()
+}
+
+/// Parses the value of a `#[cstr = "..."]` attribute, if there is one.
+fn cstr_attr(attrs: &[syn::Attribute]) -> syn::Result<Option<LitStr>> {
+    let mut name = None;
+
+    for attr in attrs {
+        if !attr.path().is_ident("cstr") {
+            continue;
+        }
+
+        let value = match &attr.meta {
+            Meta::NameValue(meta) => match &meta.value {
+                Expr::Lit(ExprLit { lit: Lit::Str(value), .. }) => value,
+                value => return Err(Error::new_spanned(value, "expected a string literal")),
+            },
+            meta => return Err(Error::new_spanned(meta, "expected `#[cstr = \"...\"]`")),
+        };
+
+        if name.is_some() {
+            return Err(Error::new_spanned(attr, "duplicate `#[cstr]` attribute"));
+        }
+
+        name = Some(value.clone());
+    }
+
+    Ok(name)
+}
@@ -0,0 +1,1220 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Implementation details of the macros exported by this crate.
+//!
+//! Everything in here is public only so that macro expansions in other crates can reach it.
+//! None of it is covered by semver.
+
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::os::raw::c_char;
+use std::sync::{Arc, Mutex, PoisonError};
+
+pub use phf::Index as PhfIndex;
+
+/// The `linkme` crate, for the expansions of `const_cstr_slice!()` and `register_cstr!()`.
+#[cfg(feature = "linkme")]
+pub mod linkme {
+    pub use linkme::*;
+}
+
+/// A C string built during `const` evaluation: the contents followed by a single NUL byte,
+/// in an array of exactly `N` bytes.
+///
+/// Used to build C strings out of arbitrary constants, which unlike literals cannot be
+/// passed to `concat!()`.
+pub struct CBuf<const N: usize> {
+    bytes: [u8; N],
+    utf8: bool,
+}
+
+impl<const N: usize> CBuf<N> {
+    /// Panics if `N` is not `concat_len(pieces) + 1` or if any piece contains a NUL byte.
+    pub const fn concat_with_nul(pieces: &[&str]) -> CBuf<N> {
+        if concat_len(pieces) + 1 != N {
+            panic!("CBuf length must be one more than the length of the string");
+        }
+
+        let mut bytes = [0; N];
+        let mut len = 0;
+        let mut i = 0;
+
+        while i < pieces.len() {
+            (bytes, len) = append(bytes, len, pieces[i].as_bytes());
+            i += 1;
+        }
+
+        check_interior_nul(&bytes);
+
+        CBuf { bytes, utf8: true }
+    }
+
+    /// Panics if `N` is not `content.len() + 1` or if `content` contains a NUL byte.
+    pub const fn bytes_with_nul(content: &[u8]) -> CBuf<N> {
+        if content.len() + 1 != N {
+            panic!("CBuf length must be one more than the length of the string");
+        }
+
+        let (bytes, _) = append([0; N], 0, content);
+
+        check_interior_nul(&bytes);
+
+        CBuf { bytes, utf8: false }
+    }
+
+    /// Encodes `s` as the "modified UTF-8" used by JNI and Java class files, which is also
+    /// CESU-8 except for NUL: NUL is encoded as `C0 80`, and characters outside the Basic
+    /// Multilingual Plane as surrogate pairs with each half encoded separately.
+    ///
+    /// Panics if `N` is not `modified_utf8_len(s) + 1`.
+    pub const fn modified_utf8_with_nul(s: &str) -> CBuf<N> {
+        if modified_utf8_len(s) + 1 != N {
+            panic!("CBuf length must be one more than the length of the string");
+        }
+
+        let bytes = s.as_bytes();
+        let mut buf = [0; N];
+        let mut len = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let (c, width) = decode_utf8(bytes, i);
+
+            if c == 0 {
+                (buf, len) = append(buf, len, &[0xC0, 0x80]);
+            } else if c >= 0x1_0000 {
+                let c = c - 0x1_0000;
+                (buf, len) = append(buf, len, &encode_surrogate(0xD800 | (c >> 10)));
+                (buf, len) = append(buf, len, &encode_surrogate(0xDC00 | (c & 0x3FF)));
+            } else {
+                let (_, rest) = bytes.split_at(i);
+                let (encoded, _) = rest.split_at(width);
+                (buf, len) = append(buf, len, encoded);
+            }
+
+            i += width;
+        }
+
+        CBuf { bytes: buf, utf8: false }
+    }
+
+    /// Panics if the buffer was not built from `str`s.
+    pub const fn as_str(&'static self) -> &'static str {
+        if !self.utf8 {
+            panic!("CBuf was not built from `str`s");
+        }
+
+        // Safe because the buffer holds `str`s followed by a NUL byte, which is still UTF-8.
+        unsafe { ::std::str::from_utf8_unchecked(&self.bytes) }
+    }
+
+    /// Panics if the buffer was not built from `str`s.
+    pub const fn as_cstr(&'static self) -> ::ConstCStr {
+        // Safe because the buffer ends with its only NUL byte.
+        unsafe { ::ConstCStr::from_str_with_nul_unchecked(self.as_str()) }
+    }
+
+    pub const fn as_cbytes(&'static self) -> ::ConstCBytes {
+        // Safe because the buffer ends with its only NUL byte.
+        unsafe { ::ConstCBytes::from_bytes_with_nul_unchecked(&self.bytes) }
+    }
+
+    pub const fn as_bytes(&'static self) -> &'static [u8] {
+        &self.bytes
+    }
+
+    pub const fn into_bytes(self) -> [u8; N] {
+        self.bytes
+    }
+}
+
+/// `bytes` as a `ConstCStr`, for the bytes of a `CBuf` built from `str`s and copied into a
+/// `static`.
+///
+/// Panics if `bytes` is not UTF-8 or is not a valid C string.
+pub const fn cstr_with_nul(bytes: &'static [u8]) -> ::ConstCStr {
+    let val = match ::std::str::from_utf8(bytes) {
+        Ok(val) => val,
+        Err(_) => panic!("C string is not UTF-8"),
+    };
+
+    match ::ConstCStr::from_str_with_nul(val) {
+        Ok(cstr) => cstr,
+        Err(_) => panic!("C string must end with its only NUL byte"),
+    }
+}
+
+/// A string built during `const` evaluation, without a NUL terminator, in an array of
+/// exactly `N` bytes.
+pub struct StrBuf<const N: usize> {
+    bytes: [u8; N],
+}
+
+impl<const N: usize> StrBuf<N> {
+    /// Panics if `N` is not `s.len()`.
+    pub const fn ascii_upper(s: &str) -> StrBuf<N> {
+        let mut buf = StrBuf::<N>::copy(s);
+        let mut i = 0;
+
+        while i < N {
+            buf.bytes[i] = buf.bytes[i].to_ascii_uppercase();
+            i += 1;
+        }
+
+        buf
+    }
+
+    /// Panics if `N` is not `s.len()`.
+    pub const fn ascii_lower(s: &str) -> StrBuf<N> {
+        let mut buf = StrBuf::<N>::copy(s);
+        let mut i = 0;
+
+        while i < N {
+            buf.bytes[i] = buf.bytes[i].to_ascii_lowercase();
+            i += 1;
+        }
+
+        buf
+    }
+
+    /// Panics if `N` is not `dedent_len(s)`.
+    pub const fn dedent(s: &str) -> StrBuf<N> {
+        if dedent_len(s) != N {
+            panic!("StrBuf length must be the length of the dedented string");
+        }
+
+        let (bytes, _) = dedent::<N>(s.as_bytes(), true);
+
+        StrBuf { bytes }
+    }
+
+    const fn copy(s: &str) -> StrBuf<N> {
+        if s.len() != N {
+            panic!("StrBuf length must be the length of the string");
+        }
+
+        let (bytes, _) = append([0; N], 0, s.as_bytes());
+
+        StrBuf { bytes }
+    }
+
+    pub const fn as_str(&'static self) -> &'static str {
+        // Safe because the buffer holds a `str`, changed only by ASCII case conversion or by
+        // removing ASCII newlines and indentation, which cannot break UTF-8 validity.
+        unsafe { ::std::str::from_utf8_unchecked(&self.bytes) }
+    }
+}
+
+/// The length of `s` after `dedent()`.
+pub const fn dedent_len(s: &str) -> usize {
+    dedent::<0>(s.as_bytes(), false).1
+}
+
+/// Removes a leading newline from `src`, then the indentation common to all of its lines
+/// that are not blank, and empties the blank ones. Returns the result and its length,
+/// which is only written to the buffer if `write` is set.
+const fn dedent<const N: usize>(src: &[u8], write: bool) -> ([u8; N], usize) {
+    let start = if !src.is_empty() && src[0] == b'\n' { 1 } else { 0 };
+
+    let mut indent = usize::MAX;
+    let mut line_start = start;
+
+    while line_start < src.len() {
+        let (width, end) = line_indent(src, line_start);
+
+        if line_start + width < end && width < indent {
+            indent = width;
+        }
+
+        line_start = end + 1;
+    }
+
+    let mut buf = [0; N];
+    let mut len = 0;
+    let mut i = start;
+    line_start = start;
+
+    while i < src.len() {
+        if i == line_start {
+            let (width, end) = line_indent(src, line_start);
+
+            // Blank lines may be indented less than the rest, so skip all their whitespace.
+            i += if line_start + width == end { width } else { indent };
+            line_start = end + 1;
+
+            if i >= src.len() {
+                break;
+            }
+        }
+
+        if write {
+            buf[len] = src[i];
+        }
+
+        len += 1;
+        i += 1;
+    }
+
+    (buf, len)
+}
+
+/// The number of spaces and tabs starting the line at `start`, and the index of the
+/// newline ending it, or `src.len()` for the last line.
+const fn line_indent(src: &[u8], start: usize) -> (usize, usize) {
+    let mut i = start;
+
+    while i < src.len() && (src[i] == b' ' || src[i] == b'\t') {
+        i += 1;
+    }
+
+    let width = i - start;
+
+    while i < src.len() && src[i] != b'\n' {
+        i += 1;
+    }
+
+    (width, i)
+}
+
+/// Copies `src` into `buf` at `len`, returning the buffer and its new length.
+const fn append<const N: usize>(mut buf: [u8; N], mut len: usize, src: &[u8]) -> ([u8; N], usize) {
+    let mut i = 0;
+
+    while i < src.len() {
+        buf[len] = src[i];
+        len += 1;
+        i += 1;
+    }
+
+    (buf, len)
+}
+
+/// Panics if `bytes` contains a NUL byte anywhere but at the very end.
+const fn check_interior_nul(bytes: &[u8]) {
+    let mut i = 0;
+
+    while i + 1 < bytes.len() {
+        if bytes[i] == 0 {
+            Message::new()
+                .push_str("C string contains an interior NUL byte at index ")
+                .push_usize(i)
+                .panic();
+        }
+
+        i += 1;
+    }
+}
+
+/// The three-byte encoding of a UTF-16 surrogate code unit, as if it were a character.
+const fn encode_surrogate(unit: u32) -> [u8; 3] {
+    [
+        0xE0 | (unit >> 12) as u8,
+        0x80 | ((unit >> 6) & 0x3F) as u8,
+        0x80 | (unit & 0x3F) as u8,
+    ]
+}
+
+/// The length of `s` encoded as modified UTF-8.
+pub const fn modified_utf8_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut len = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let width = utf8_width(bytes[i]);
+
+        len += match (bytes[i], width) {
+            (0, _) => 2,
+            // A surrogate pair, at three bytes per half.
+            (_, 4) => 6,
+            (_, width) => width,
+        };
+        i += width;
+    }
+
+    len
+}
+
+/// The path of the function containing `marker`, a function item named
+/// `__const_cstr_func`, as a leaked C string.
+pub fn func_name<F>(_marker: F) -> ::ConstCStr {
+    let mut path = ::std::any::type_name::<F>();
+
+    path = path.trim_end_matches("::__const_cstr_func");
+
+    while path.ends_with("::{{closure}}") {
+        path = &path[..path.len() - "::{{closure}}".len()];
+    }
+
+    let val = Box::leak(format!("{}\0", path).into_boxed_str());
+    // Safe because Rust paths do not contain NUL bytes.
+    unsafe { ::ConstCStr::from_str_with_nul_unchecked(val) }
+}
+
+/// The C string last formatted by an invocation of `cstr_format!()`.
+#[derive(Default)]
+pub struct FormatCache {
+    cached: Mutex<Option<Arc<CStr>>>,
+}
+
+impl FormatCache {
+    pub const fn new() -> FormatCache {
+        FormatCache { cached: Mutex::new(None) }
+    }
+
+    /// Returns the cached C string if `args` still format to it, and otherwise a new one
+    /// holding them that replaces it.
+    ///
+    /// `args` are formatted before taking the lock, so that `Display` impls run by them
+    /// can't deadlock by using the same invocation.
+    ///
+    /// Panics if the formatted string contains a NUL byte.
+    pub fn get(&self, args: fmt::Arguments) -> Arc<CStr> {
+        let formatted = fmt::format(args);
+        let mut cached = self.cached.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(ref cstr) = *cached {
+            if cstr.to_bytes() == formatted.as_bytes() {
+                return cstr.clone();
+            }
+        }
+
+        let cstr: Arc<CStr> = match CString::new(formatted) {
+            Ok(cstr) => cstr.into(),
+            Err(err) => panic!(
+                "formatted C string contains an interior NUL byte at index {}",
+                err.nul_position()
+            ),
+        };
+
+        *cached = Some(cstr.clone());
+        cstr
+    }
+}
+
+/// Panics if `value` is longer than `max_len` bytes.
+pub const fn check_max_len(name: &str, value: &str, max_len: usize) {
+    if value.len() > max_len {
+        Message::new()
+            .push_str("`")
+            .push_str(name)
+            .push_str("` is ")
+            .push_usize(value.len())
+            .push_str(" bytes long, more than its `max_len` of ")
+            .push_usize(max_len)
+            .panic();
+    }
+}
+
+/// Panics if `value` contains a non-ASCII character.
+pub const fn check_ascii(name: &str, value: &str) {
+    let bytes = value.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii() {
+            let (_, rest) = bytes.split_at(i);
+            let (c, _) = rest.split_at(utf8_width(bytes[i]));
+            // Safe because `c` is a whole character of `value`.
+            let c = unsafe { ::std::str::from_utf8_unchecked(c) };
+
+            Message::new()
+                .push_str("`")
+                .push_str(name)
+                .push_str("` contains the non-ASCII character `")
+                .push_str(c)
+                .push_str("` at index ")
+                .push_usize(i)
+                .panic();
+        }
+
+        i += 1;
+    }
+}
+
+/// `ident` without the `r#` prefix of a raw identifier, if it has one.
+pub const fn unraw(ident: &'static str) -> &'static str {
+    let bytes = ident.as_bytes();
+
+    if bytes.len() > 2 && bytes[0] == b'r' && bytes[1] == b'#' {
+        let (_, rest) = bytes.split_at(2);
+        // Safe because splitting off an ASCII prefix cannot break UTF-8 validity.
+        unsafe { ::std::str::from_utf8_unchecked(rest) }
+    } else {
+        ident
+    }
+}
+
+/// The combined length of `pieces`.
+pub const fn concat_len(pieces: &[&str]) -> usize {
+    let mut len = 0;
+    let mut i = 0;
+
+    while i < pieces.len() {
+        len += pieces[i].len();
+        i += 1;
+    }
+
+    len
+}
+
+/// A UTF-16 string built during `const` evaluation: the encoded contents followed by a
+/// single NUL, in an array of exactly `N` code units.
+pub struct WBuf<const N: usize> {
+    units: [u16; N],
+}
+
+impl<const N: usize> WBuf<N> {
+    /// Panics if `N` is not `utf16_len(s) + 1` or if `s` contains a NUL.
+    pub const fn encode_with_nul(s: &str) -> WBuf<N> {
+        if utf16_len(s) + 1 != N {
+            panic!("WBuf length must be one more than the UTF-16 length of the string");
+        }
+
+        WBuf { units: encode_utf16(s, false) }
+    }
+
+    /// Encodes `s` as is, for a string that already holds its NULs, such as a `MULTI_SZ`
+    /// list.
+    ///
+    /// Panics if `N` is not `utf16_len(s)`.
+    pub const fn encode_with_nuls(s: &str) -> WBuf<N> {
+        if utf16_len(s) != N {
+            panic!("WBuf length must be the UTF-16 length of the string");
+        }
+
+        WBuf { units: encode_utf16(s, true) }
+    }
+
+    pub const fn as_wide(&'static self) -> &'static [u16] {
+        &self.units
+    }
+}
+
+/// Encodes `s` as UTF-16 at the start of `N` code units, leaving the rest zeroed.
+///
+/// Panics if `s` contains a NUL and `allow_nul` is not set.
+const fn encode_utf16<const N: usize>(s: &str, allow_nul: bool) -> [u16; N] {
+    let bytes = s.as_bytes();
+    let mut units = [0; N];
+    let mut len = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let (c, width) = decode_utf8(bytes, i);
+
+        if c == 0 && !allow_nul {
+            Message::new()
+                .push_str("wide string contains an interior NUL at index ")
+                .push_usize(len)
+                .panic();
+        }
+
+        if c >= 0x1_0000 {
+            let c = c - 0x1_0000;
+            units[len] = 0xD800 | (c >> 10) as u16;
+            units[len + 1] = 0xDC00 | (c & 0x3FF) as u16;
+            len += 2;
+        } else {
+            units[len] = c as u16;
+            len += 1;
+        }
+
+        i += width;
+    }
+
+    units
+}
+
+/// The number of UTF-16 code units needed to encode `s`.
+pub const fn utf16_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut len = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let width = utf8_width(bytes[i]);
+        // Only 4-byte sequences are outside the Basic Multilingual Plane.
+        len += if width == 4 { 2 } else { 1 };
+        i += width;
+    }
+
+    len
+}
+
+/// A UTF-32 string built during `const` evaluation: the encoded contents followed by a
+/// single NUL, in an array of exactly `N` code units.
+pub struct W32Buf<const N: usize> {
+    units: [u32; N],
+}
+
+impl<const N: usize> W32Buf<N> {
+    /// Panics if `N` is not `char_count(s) + 1` or if `s` contains a NUL.
+    pub const fn encode_with_nul(s: &str) -> W32Buf<N> {
+        if char_count(s) + 1 != N {
+            panic!("W32Buf length must be one more than the number of characters");
+        }
+
+        let bytes = s.as_bytes();
+        let mut units = [0; N];
+        let mut len = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let (c, width) = decode_utf8(bytes, i);
+
+            if c == 0 {
+                Message::new()
+                    .push_str("wide string contains an interior NUL at index ")
+                    .push_usize(len)
+                    .panic();
+            }
+
+            units[len] = c;
+            len += 1;
+            i += width;
+        }
+
+        W32Buf { units }
+    }
+
+    pub const fn as_wide(&'static self) -> &'static [u32] {
+        &self.units
+    }
+}
+
+/// A UCS-2 string built during `const` evaluation: the encoded contents followed by a
+/// single NUL, in an array of exactly `N` code units.
+pub struct Ucs2Buf<const N: usize> {
+    units: [u16; N],
+}
+
+impl<const N: usize> Ucs2Buf<N> {
+    /// Panics if `N` is not `char_count(s) + 1`, if `s` contains a NUL or if `s` contains a
+    /// character outside the Basic Multilingual Plane.
+    pub const fn encode_with_nul(s: &str) -> Ucs2Buf<N> {
+        if char_count(s) + 1 != N {
+            panic!("Ucs2Buf length must be one more than the number of characters");
+        }
+
+        let bytes = s.as_bytes();
+        let mut units = [0; N];
+        let mut len = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let (c, width) = decode_utf8(bytes, i);
+
+            if c == 0 {
+                Message::new()
+                    .push_str("UCS-2 string contains an interior NUL at index ")
+                    .push_usize(len)
+                    .panic();
+            }
+
+            if c >= 0x1_0000 {
+                Message::new()
+                    .push_str("UCS-2 string contains a character outside the Basic Multilingual \
+                               Plane at index ")
+                    .push_usize(len)
+                    .panic();
+            }
+
+            units[len] = c as u16;
+            len += 1;
+            i += width;
+        }
+
+        Ucs2Buf { units }
+    }
+
+    pub const fn as_cstr16(&'static self) -> ::ConstCStr16 {
+        // Safe because the buffer ends with its only NUL.
+        unsafe { ::ConstCStr16::from_wide_with_nul_unchecked(&self.units) }
+    }
+}
+
+/// The number of characters in `s`, i.e. of UTF-32 code units needed to encode it.
+pub const fn char_count(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut count = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        count += 1;
+        i += utf8_width(bytes[i]);
+    }
+
+    count
+}
+
+/// Decodes the character starting at `bytes[i]`, returning it and its encoded width.
+const fn decode_utf8(bytes: &[u8], i: usize) -> (u32, usize) {
+    let width = utf8_width(bytes[i]);
+    let mut c = match width {
+        1 => bytes[i] as u32,
+        2 => (bytes[i] & 0x1F) as u32,
+        3 => (bytes[i] & 0x0F) as u32,
+        _ => (bytes[i] & 0x07) as u32,
+    };
+    let mut j = 1;
+
+    while j < width {
+        c = (c << 6) | (bytes[i + j] & 0x3F) as u32;
+        j += 1;
+    }
+
+    (c, width)
+}
+
+/// Wraps the argument of `const_cstr!()`, dispatching on its type so that string slices
+/// produce a `ConstCStr` and byte strings produce a `ConstCBytes`.
+pub struct Src<T>(pub T);
+
+impl Src<&'static str> {
+    pub const fn as_bytes(&self) -> &'static [u8] {
+        self.0.as_bytes()
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        self.0
+    }
+
+    pub const fn content_len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub const fn with_nul<const N: usize>(&self) -> CBuf<N> {
+        CBuf::concat_with_nul(&[self.0])
+    }
+
+    pub const fn finish<const N: usize>(&self, buf: &'static CBuf<N>) -> ::ConstCStr {
+        buf.as_cstr()
+    }
+}
+
+impl Src<&'static CStr> {
+    pub const fn as_bytes(&self) -> &'static [u8] {
+        self.0.to_bytes()
+    }
+
+    /// Panics if the string is not UTF-8.
+    pub const fn as_str(&self) -> &'static str {
+        ::ConstCStr::from_cstr(self.0).to_str()
+    }
+
+    pub const fn content_len(&self) -> usize {
+        self.as_bytes().len()
+    }
+
+    pub const fn with_nul<const N: usize>(&self) -> CBuf<N> {
+        CBuf::concat_with_nul(&[self.as_str()])
+    }
+
+    pub const fn finish<const N: usize>(&self, buf: &'static CBuf<N>) -> ::ConstCStr {
+        buf.as_cstr()
+    }
+}
+
+impl<const M: usize> Src<&'static [u8; M]> {
+    pub const fn as_bytes(&self) -> &'static [u8] {
+        self.0
+    }
+
+    pub const fn content_len(&self) -> usize {
+        M
+    }
+
+    pub const fn with_nul<const N: usize>(&self) -> CBuf<N> {
+        CBuf::bytes_with_nul(self.0)
+    }
+
+    pub const fn finish<const N: usize>(&self, buf: &'static CBuf<N>) -> ::ConstCBytes {
+        buf.as_cbytes()
+    }
+}
+
+impl Src<&'static [u8]> {
+    pub const fn as_bytes(&self) -> &'static [u8] {
+        self.0
+    }
+
+    pub const fn content_len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub const fn with_nul<const N: usize>(&self) -> CBuf<N> {
+        CBuf::bytes_with_nul(self.0)
+    }
+
+    pub const fn finish<const N: usize>(&self, buf: &'static CBuf<N>) -> ::ConstCBytes {
+        buf.as_cbytes()
+    }
+}
+
+/// `content` followed by NUL bytes, filling an array of `N` `c_char`s.
+///
+/// Panics if `content` and at least one NUL do not fit, or if `content` contains a NUL.
+pub const fn padded<const N: usize>(content: &[u8]) -> [c_char; N] {
+    if content.len() + 1 > N {
+        Message::new()
+            .push_str("C string needs ")
+            .push_usize(content.len() + 1)
+            .push_str(" bytes including the NUL terminator, but the array only has ")
+            .push_usize(N)
+            .panic();
+    }
+
+    let mut array = [0; N];
+    let mut i = 0;
+
+    while i < content.len() {
+        if content[i] == 0 {
+            Message::new()
+                .push_str("C string contains an interior NUL byte at index ")
+                .push_usize(i)
+                .panic();
+        }
+
+        array[i] = content[i] as c_char;
+        i += 1;
+    }
+
+    array
+}
+
+/// The length of the pool that `pool_bytes()` builds from `strs`.
+pub const fn pool_len(strs: &[&str]) -> usize {
+    let (_, len) = pool_layout::<0>(strs, false);
+    len
+}
+
+/// Lays out `strs` in a single buffer of NUL-terminated strings, storing each one only if
+/// it is not already in the buffer, either as one of the strings or as the end of one.
+/// Longer strings are placed first, so that shorter ones can share their ends.
+///
+/// Panics if `N` is not `pool_len(strs)` or if any string contains a NUL byte.
+pub const fn pool_bytes<const N: usize>(strs: &[&str]) -> [u8; N] {
+    let (bytes, len) = pool_layout::<N>(strs, true);
+
+    if len != N {
+        panic!("pool length must be `pool_len(strs)`");
+    }
+
+    bytes
+}
+
+/// Builds the pool if `write` is set, returning it and its length, or otherwise returns its
+/// length alone, so that the length can be computed before `N` is known.
+const fn pool_layout<const N: usize>(strs: &[&str], write: bool) -> ([u8; N], usize) {
+    let mut bytes = [0; N];
+    let mut len = 0;
+    let mut max = usize::MAX;
+
+    // Each round visits the strings of the greatest length below that of the last round.
+    loop {
+        let mut round = None;
+        let mut i = 0;
+
+        while i < strs.len() {
+            let str_len = strs[i].len();
+
+            match round {
+                Some(round_len) if str_len <= round_len => {}
+                _ if str_len < max => round = Some(str_len),
+                _ => {}
+            }
+
+            i += 1;
+        }
+
+        let round = match round {
+            Some(round) => round,
+            None => return (bytes, len),
+        };
+
+        i = 0;
+
+        while i < strs.len() {
+            if strs[i].len() == round {
+                if let Some(index) = find_nul(strs[i].as_bytes()) {
+                    Message::new()
+                        .push_str("C string contains an interior NUL byte at index ")
+                        .push_usize(index)
+                        .panic();
+                }
+
+                if !pool_shared(strs, i) {
+                    if write {
+                        (bytes, len) = append(bytes, len, strs[i].as_bytes());
+                        (bytes, len) = append(bytes, len, &[0]);
+                    } else {
+                        len += round + 1;
+                    }
+                }
+            }
+
+            i += 1;
+        }
+
+        max = round;
+    }
+}
+
+/// Whether `strs[i]` is the end of a string visited before it, and so is already in the
+/// pool.
+const fn pool_shared(strs: &[&str], i: usize) -> bool {
+    let content = strs[i].as_bytes();
+    let mut j = 0;
+
+    while j < strs.len() {
+        let other = strs[j].as_bytes();
+        let before = other.len() > content.len() || (other.len() == content.len() && j < i);
+
+        if before && ends_with(other, content) {
+            return true;
+        }
+
+        j += 1;
+    }
+
+    false
+}
+
+const fn find_nul(bytes: &[u8]) -> Option<usize> {
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == 0 {
+            return Some(i);
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+const fn ends_with(bytes: &[u8], suffix: &[u8]) -> bool {
+    if suffix.len() > bytes.len() {
+        return false;
+    }
+
+    let start = bytes.len() - suffix.len();
+    let mut i = 0;
+
+    while i < suffix.len() {
+        if bytes[start + i] != suffix[i] {
+            return false;
+        }
+
+        i += 1;
+    }
+
+    true
+}
+
+/// The offset of `content` followed by a NUL in `pool`, if it is there.
+const fn pool_find(pool: &[u8], content: &[u8]) -> Option<usize> {
+    let mut end = 0;
+
+    while end < pool.len() {
+        if pool[end] == 0 {
+            let (string, _) = pool.split_at(end);
+
+            if ends_with(string, content) {
+                return Some(end - content.len());
+            }
+        }
+
+        end += 1;
+    }
+
+    None
+}
+
+/// `s` as stored in `pool`, which was built by `pool_bytes()` and copied into a `static`
+/// from `layout`.
+///
+/// Panics if `s` is not in the pool.
+pub const fn pool_cstr<const N: usize>(
+    pool: &'static [u8; N],
+    layout: &[u8; N],
+    s: &str,
+) -> ::ConstCStr {
+    let offset = match pool_find(layout, s.as_bytes()) {
+        Some(offset) => offset,
+        None => panic!("string is not in the pool"),
+    };
+
+    let (_, rest) = pool.split_at(offset);
+    let (bytes, _) = rest.split_at(s.len() + 1);
+    // Safe because the bytes are equal to those of `s` followed by a NUL, and `s` was
+    // checked for NUL bytes when the pool was built.
+    unsafe { ::ConstCStr::from_str_with_nul_unchecked(::std::str::from_utf8_unchecked(bytes)) }
+}
+
+/// The separator `const_cstr_path!()` joins path segments with.
+#[cfg(windows)]
+pub const PATH_SEPARATOR: &str = "\\";
+#[cfg(not(windows))]
+pub const PATH_SEPARATOR: &str = "/";
+
+/// Wraps an argument of `const_cstr_concat!()` so that both string slices and `ConstCStr`s
+/// can be turned into their contents during `const` evaluation.
+pub struct Piece<T>(pub T);
+
+impl Piece<&'static str> {
+    pub const fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl Piece<::ConstCStr> {
+    pub const fn as_str(&self) -> &'static str {
+        self.0.to_str()
+    }
+}
+
+/// The length of the `MULTI_SZ` list that `multi_sz_bytes()` builds from `strs`.
+pub const fn multi_sz_len(strs: &[&str]) -> usize {
+    if strs.is_empty() {
+        2
+    } else {
+        concat_len(strs) + strs.len() + 1
+    }
+}
+
+/// Lays out `strs` as a `MULTI_SZ` list: each followed by a NUL byte, then an extra NUL.
+///
+/// Panics if `N` is not `multi_sz_len(strs)`, or if any string is empty or contains a NUL
+/// byte, since either would end the list early.
+pub const fn multi_sz_bytes<const N: usize>(strs: &[&str]) -> [u8; N] {
+    if multi_sz_len(strs) != N {
+        panic!("MULTI_SZ length must be `multi_sz_len(strs)`");
+    }
+
+    let mut bytes = [0; N];
+    let mut len = 0;
+    let mut i = 0;
+
+    while i < strs.len() {
+        if strs[i].is_empty() {
+            Message::new()
+                .push_str("MULTI_SZ entry ")
+                .push_usize(i)
+                .push_str(" is empty")
+                .panic();
+        }
+
+        if let Some(index) = find_nul(strs[i].as_bytes()) {
+            Message::new()
+                .push_str("MULTI_SZ entry ")
+                .push_usize(i)
+                .push_str(" contains an interior NUL byte at index ")
+                .push_usize(index)
+                .panic();
+        }
+
+        (bytes, len) = append(bytes, len, strs[i].as_bytes());
+        (bytes, len) = append(bytes, len, &[0]);
+        i += 1;
+    }
+
+    bytes
+}
+
+/// `bytes` as a `ConstMultiSz`, for the output of `multi_sz_bytes()` copied into a
+/// `static`.
+///
+/// Panics if `bytes` is not UTF-8.
+pub const fn multi_sz(bytes: &'static [u8]) -> ::ConstMultiSz {
+    match ::std::str::from_utf8(bytes) {
+        Ok(val) => ::ConstMultiSz::from_str_with_nuls(val),
+        Err(_) => panic!("MULTI_SZ list is not UTF-8"),
+    }
+}
+
+/// `key` unchanged, for `const_envp!()`.
+///
+/// Panics if `key` is empty or contains a `=`, which would change where the value begins.
+pub const fn env_key(key: &'static str) -> &'static str {
+    let bytes = key.as_bytes();
+    let mut i = 0;
+
+    if bytes.is_empty() {
+        panic!("environment variable key is empty");
+    }
+
+    while i < bytes.len() {
+        if bytes[i] == b'=' {
+            Message::new()
+                .push_str("environment variable key `")
+                .push_str(key)
+                .push_str("` contains a `=` at index ")
+                .push_usize(i)
+                .panic();
+        }
+
+        i += 1;
+    }
+
+    key
+}
+
+/// `name` unchanged, for `vk_names!()`.
+///
+/// Panics if `name` does not fit in `VK_MAX_EXTENSION_NAME_SIZE` bytes with its NUL byte.
+#[cfg(feature = "ash")]
+pub const fn vk_name(name: &'static str) -> &'static str {
+    if name.len() >= ::ash::vk::MAX_EXTENSION_NAME_SIZE {
+        Message::new()
+            .push_str("Vulkan name `")
+            .push_str(name)
+            .push_str("` is ")
+            .push_usize(name.len())
+            .push_str(" bytes long, but only ")
+            .push_usize(::ash::vk::MAX_EXTENSION_NAME_SIZE - 1)
+            .push_str(" fit in `VK_MAX_EXTENSION_NAME_SIZE` with the NUL byte")
+            .panic();
+    }
+
+    name
+}
+
+/// A pointer to each of `strs` followed by a null pointer, for `const_cstr_ptr_array!()`.
+///
+/// Panics if `N` is not `strs.len() + 1`.
+pub const fn ptrs_with_null<const N: usize>(strs: &[::ConstCStr]) -> [*const c_char; N] {
+    if strs.len() + 1 != N {
+        panic!("pointer array length must be one more than the number of strings");
+    }
+
+    let mut ptrs = [::std::ptr::null(); N];
+    let mut i = 0;
+
+    while i < strs.len() {
+        ptrs[i] = strs[i].as_ptr();
+        i += 1;
+    }
+
+    ptrs
+}
+
+/// Wraps an argument of `const_argv!()` so that both `ConstCStr`s and `ConstCBytes` can be
+/// turned into pointers during `const` evaluation.
+pub struct Ptr<T>(pub T);
+
+impl Ptr<::ConstCStr> {
+    pub const fn as_ptr(&self) -> *const c_char {
+        self.0.as_ptr()
+    }
+}
+
+impl Ptr<::ConstCBytes> {
+    pub const fn as_ptr(&self) -> *const c_char {
+        self.0.as_ptr()
+    }
+}
+
+/// The all-zero value of `T`, for the sentinel entries of `const_cstr_table!()`.
+///
+/// An associated constant rather than a function so that it is always evaluated at compile
+/// time, where a `T` for which zero is not a valid value is a build error rather than
+/// undefined behavior.
+pub struct Zeroed<T>(PhantomData<T>);
+
+impl<T> Zeroed<T> {
+    pub const VALUE: T = unsafe { mem::zeroed() };
+}
+
+const MESSAGE_CAP: usize = 128;
+
+/// A fixed-capacity buffer for building panic messages during `const` evaluation,
+/// where `format!()` is not available.
+pub struct Message {
+    buf: [u8; MESSAGE_CAP],
+    len: usize,
+}
+
+#[allow(clippy::new_without_default)]
+impl Message {
+    pub const fn new() -> Message {
+        Message { buf: [0; MESSAGE_CAP], len: 0 }
+    }
+
+    /// Appends `s`, silently truncating (at a character boundary) once the buffer is full.
+    pub const fn push_str(mut self, s: &str) -> Message {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let width = utf8_width(bytes[i]);
+
+            if self.len + width > MESSAGE_CAP {
+                break;
+            }
+
+            let mut j = 0;
+
+            while j < width {
+                self.buf[self.len] = bytes[i + j];
+                self.len += 1;
+                j += 1;
+            }
+
+            i += width;
+        }
+
+        self
+    }
+
+    pub const fn push_usize(mut self, mut n: usize) -> Message {
+        let mut digits = [0u8; 20];
+        let mut count = 0;
+
+        loop {
+            digits[count] = b'0' + (n % 10) as u8;
+            count += 1;
+            n /= 10;
+
+            if n == 0 {
+                break;
+            }
+        }
+
+        while count > 0 && self.len < MESSAGE_CAP {
+            count -= 1;
+            self.buf[self.len] = digits[count];
+            self.len += 1;
+        }
+
+        self
+    }
+
+    pub const fn panic(&self) -> ! {
+        let (bytes, _) = self.buf.split_at(self.len);
+        // Safe because only whole UTF-8 encoded characters are ever pushed.
+        let msg = unsafe { ::std::str::from_utf8_unchecked(bytes) };
+
+        panic!("{}", msg)
+    }
+}
+
+/// The width of the UTF-8 sequence that starts with `byte`.
+const fn utf8_width(byte: u8) -> usize {
+    match byte {
+        0xF0..=0xFF => 4,
+        0xE0..=0xEF => 3,
+        0xC0..=0xDF => 2,
+        _ => 1,
+    }
+}
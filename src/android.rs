@@ -0,0 +1,129 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Android logging with constant tags, enabled by the `android` feature on Android.
+use std::os::raw::{c_char, c_int};
+use std::ffi::{CStr, CString};
+use std::fmt;
+
+use ConstCStr;
+
+#[link(name = "log")]
+extern "C" {
+    fn __android_log_write(prio: c_int, tag: *const c_char, text: *const c_char) -> c_int;
+    fn __android_log_print(prio: c_int, tag: *const c_char, fmt: *const c_char, ...) -> c_int;
+}
+
+/// The priority of a log message, from `android/log.h`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(i32)]
+pub enum LogPriority {
+    /// `ANDROID_LOG_VERBOSE`.
+    Verbose = 2,
+    /// `ANDROID_LOG_DEBUG`.
+    Debug = 3,
+    /// `ANDROID_LOG_INFO`.
+    Info = 4,
+    /// `ANDROID_LOG_WARN`.
+    Warn = 5,
+    /// `ANDROID_LOG_ERROR`.
+    Error = 6,
+    /// `ANDROID_LOG_FATAL`.
+    Fatal = 7,
+}
+
+/// A tag for Android log messages, checked to be no longer than `LogTag::MAX_LEN`, usually
+/// created by the `log_tag!` macro so that the check happens at compile time.
+///
+/// ```rust,ignore
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::{LogPriority, LogTag};
+///
+/// const TAG: LogTag = log_tag!("MyNativeLib");
+///
+/// TAG.write(LogPriority::Info, const_cstr!("initialized"));
+/// TAG.print(LogPriority::Warn, format_args!("retrying in {} ms", 500));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LogTag(ConstCStr);
+
+impl LogTag {
+    /// The maximum length of a tag in bytes, **without** the NUL terminating byte, beyond
+    /// which Android versions before 8.0 fail `__android_log_is_loggable()` checks.
+    pub const MAX_LEN: usize = 23;
+
+    /// Wraps `tag`.
+    ///
+    /// Panics
+    /// ------
+    /// If `tag` is longer than `MAX_LEN`, which fails the build in a `const`.
+    pub const fn new(tag: ConstCStr) -> LogTag {
+        if tag.len() > LogTag::MAX_LEN {
+            panic!("Android log tag is longer than `LogTag::MAX_LEN` (23 bytes)");
+        }
+
+        LogTag(tag)
+    }
+
+    /// Returns the wrapped tag.
+    pub const fn as_cstr(&self) -> ConstCStr {
+        self.0
+    }
+
+    /// Logs `text` with this tag, as is, through `__android_log_write()`.
+    ///
+    /// Returns what that returns: 1 if the message was written, or a negative error code.
+    pub fn write<T: AsRef<CStr>>(&self, prio: LogPriority, text: T) -> c_int {
+        // Safe because both strings are NUL-terminated and outlive the call.
+        unsafe { __android_log_write(prio as c_int, self.0.as_ptr(), text.as_ref().as_ptr()) }
+    }
+
+    /// Formats `args` and logs the result with this tag through `__android_log_print()`,
+    /// passing it as the argument to a `%s`, so that `%` in the message is never
+    /// interpreted.
+    ///
+    /// The message is cut off at the first NUL byte, if it has one.
+    ///
+    /// Returns what `__android_log_print()` returns: 1 if the message was written, or a
+    /// negative error code.
+    pub fn print(&self, prio: LogPriority, args: fmt::Arguments) -> c_int {
+        let text = CString::new(fmt::format(args)).unwrap_or_else(|err| {
+            let nul = err.nul_position();
+            let mut bytes = err.into_vec();
+            bytes.truncate(nul);
+            // Safe because the bytes were cut off before the first NUL byte.
+            unsafe { CString::from_vec_unchecked(bytes) }
+        });
+
+        // Safe because the format string consumes exactly the one string argument, and all
+        // the strings are NUL-terminated and outlive the call.
+        unsafe {
+            __android_log_print(
+                prio as c_int,
+                self.0.as_ptr(),
+                const_cstr!("%s").as_ptr(),
+                text.as_ptr(),
+            )
+        }
+    }
+}
+
+impl From<LogTag> for ConstCStr {
+    fn from(tag: LogTag) -> ConstCStr {
+        tag.0
+    }
+}
+
+#[test]
+fn test_log_tag() {
+    const TAG: LogTag = log_tag!("const-cstr-test");
+
+    assert_eq!(TAG.as_cstr().to_str(), "const-cstr-test");
+    assert_eq!(TAG.write(LogPriority::Debug, const_cstr!("write")), 1);
+    assert_eq!(TAG.print(LogPriority::Debug, format_args!("print {}%", 100)), 1);
+}
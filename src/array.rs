@@ -0,0 +1,50 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+
+use ConstCStr;
+
+/// Extension methods for arrays of C string constants, such as those declared by the
+/// `const_cstr_array!` macro.
+pub trait AsPtrs<const N: usize> {
+    /// Returns a pointer to each of the strings, in an array suitable for C APIs that take
+    /// a `const char* const*` and a count.
+    ///
+    /// The pointers are valid forever, but the returned array is not; keep it alive for as
+    /// long as C holds on to a pointer to it.
+    fn as_ptrs(&self) -> [*const c_char; N];
+}
+
+impl<const N: usize> AsPtrs<N> for [ConstCStr; N] {
+    fn as_ptrs(&self) -> [*const c_char; N] {
+        self.map(|cstr| cstr.as_ptr())
+    }
+}
+
+#[cfg(test)]
+mod test_const_cstr_array_mod {
+    const_cstr_array! {
+        pub static NAMES = ["first", concat!("sec", "ond")];
+        /// Documented.
+        pub(crate) const EMPTY = [];
+    }
+}
+
+#[test]
+fn test_const_cstr_array() {
+    use std::ffi::CStr;
+    use self::test_const_cstr_array_mod::*;
+
+    let names: &'static [ConstCStr; 2] = &NAMES;
+    let ptrs = names.as_ptrs();
+
+    assert_eq!(names[1].to_str(), "second");
+    assert_eq!(unsafe { CStr::from_ptr(ptrs[0]) }.to_str(), Ok("first"));
+    assert_eq!(EMPTY.as_ptrs().len(), 0);
+}
@@ -0,0 +1,61 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Vulkan extension and layer name lists for `ash`, enabled by the `ash` feature.
+use std::os::raw::c_char;
+
+use ConstCStrArray;
+
+impl ConstCStrArray {
+    /// Returns the array of pointers to the strings, **without** the null pointer at the
+    /// end, as taken by the `enabled_extension_names()` and `enabled_layer_names()` methods
+    /// of `ash::vk::InstanceCreateInfo` and `ash::vk::DeviceCreateInfo`.
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// # extern crate ash;
+    /// use ash::vk;
+    /// use const_cstr::ConstCStrArray;
+    ///
+    /// static EXTENSIONS: ConstCStrArray = vk_names!["VK_KHR_surface", "VK_KHR_xlib_surface"];
+    /// static LAYERS: ConstCStrArray = vk_names!["VK_LAYER_KHRONOS_validation"];
+    /// # fn main() {
+    /// let info = vk::InstanceCreateInfo::default()
+    ///     .enabled_extension_names(EXTENSIONS.as_vk_names())
+    ///     .enabled_layer_names(LAYERS.as_vk_names());
+    ///
+    /// assert_eq!(info.enabled_extension_count, 2);
+    /// # }
+    /// ```
+    pub fn as_vk_names(&self) -> &'static [*const c_char] {
+        &self.as_ptrs_with_null()[..self.len()]
+    }
+}
+
+#[test]
+fn test_vk_names() {
+    use ash::vk;
+    use std::ffi::CStr;
+
+    static EXTENSIONS: ConstCStrArray = vk_names![
+        "VK_KHR_swapchain",
+        const_cstr!("VK_KHR_maintenance1"),
+    ];
+    const NONE: ConstCStrArray = vk_names![];
+
+    let device_info =
+        vk::DeviceCreateInfo::default().enabled_extension_names(EXTENSIONS.as_vk_names());
+    let instance_info = vk::InstanceCreateInfo::default().enabled_layer_names(NONE.as_vk_names());
+
+    assert_eq!(device_info.enabled_extension_count, 2);
+    assert_eq!(
+        unsafe { CStr::from_ptr(*device_info.pp_enabled_extension_names.add(1)) },
+        vk::KHR_MAINTENANCE1_NAME
+    );
+    assert_eq!(instance_info.enabled_layer_count, 0);
+}
@@ -0,0 +1,236 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+use std::borrow::Borrow;
+use std::fmt;
+use std::ffi::{CStr, CString};
+use std::ops::Deref;
+use std::str::{self, Utf8Error};
+
+use ConstCStr;
+
+/// A type representing a static C-compatible string that is not necessarily valid UTF-8,
+/// wrapping `&'static [u8]`.
+///
+/// Created by passing a byte string to the `const_cstr!` macro:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstCBytes;
+///
+/// const LEGACY: ConstCBytes = const_cstr!(b"\x80\xFFlegacy");
+/// # fn main() {
+/// assert_eq!(LEGACY.to_bytes(), b"\x80\xFFlegacy");
+/// assert!(LEGACY.to_str().is_err());
+/// # }
+/// ```
+///
+/// It is otherwise used like a `ConstCStr`, and derefs and borrows as `CStr` the same way.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConstCBytes {
+    /// Ends with its only NUL byte.
+    val: &'static [u8],
+}
+
+impl ConstCBytes {
+    /// Wraps any `&'static CStr`, such as a `c"..."` literal.
+    pub const fn from_cstr(cstr: &'static CStr) -> ConstCBytes {
+        ConstCBytes { val: cstr.to_bytes_with_nul() }
+    }
+
+    /// Wraps a byte string that already ends with a NUL byte, without checking it.
+    ///
+    /// Safety
+    /// ------
+    /// `val` must end with a NUL byte and must not contain any other, as for
+    /// `CStr::from_bytes_with_nul_unchecked()`.
+    pub const unsafe fn from_bytes_with_nul_unchecked(val: &'static [u8]) -> ConstCBytes {
+        ConstCBytes { val }
+    }
+
+    /// Returns the wrapped string as a byte slice, **without** the NUL terminating byte.
+    pub const fn to_bytes(&self) -> &'static [u8] {
+        let (content, _) = self.val.split_at(self.val.len() - 1);
+        content
+    }
+
+    /// Returns the wrapped string as a byte slice, **with** the NUL terminating byte.
+    pub const fn to_bytes_with_nul(&self) -> &'static [u8] {
+        self.val
+    }
+
+    /// Returns the length of the wrapped string in bytes, **without** the NUL terminating
+    /// byte, like `strlen()`.
+    pub const fn len(&self) -> usize {
+        self.val.len() - 1
+    }
+
+    /// Returns the length of the wrapped string in bytes, **with** the NUL terminating byte.
+    pub const fn len_with_nul(&self) -> usize {
+        self.val.len()
+    }
+
+    /// Returns `true` if the wrapped string has no bytes before the NUL terminating byte.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the wrapped string, without the NUL terminating byte, if it is valid UTF-8.
+    pub fn to_str(&self) -> Result<&'static str, Utf8Error> {
+        str::from_utf8(self.to_bytes())
+    }
+
+    /// Returns a pointer to the beginning of the wrapped string.
+    ///
+    /// Suitable for passing to any function that expects a C-compatible string.
+    /// Since the underlying string is guaranteed to be `'static`,
+    /// the pointer should always be valid.
+    pub const fn as_ptr(&self) -> *const c_char {
+        self.val.as_ptr() as *const c_char
+    }
+
+    /// Returns the wrapped string as an `&'static CStr`, skipping the length check that
+    /// `CStr::from_ptr()` performs (since we know the length already).
+    pub const fn as_cstr(&self) -> &'static CStr {
+        // Safe because the wrapped string ends with its only NUL byte.
+        unsafe { CStr::from_bytes_with_nul_unchecked(self.val) }
+    }
+
+    /// Copies the wrapped string into a new `CString`, for C APIs that take ownership of
+    /// the string or modify it.
+    pub fn to_cstring(&self) -> CString {
+        self.as_cstr().to_owned()
+    }
+}
+
+/// Writes the string as a quoted and escaped literal without the NUL terminating byte, like
+/// `CStr` does.
+impl fmt::Debug for ConstCBytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_cstr(), f)
+    }
+}
+
+/// Makes all of `CStr`'s methods available, and lets `&ConstCBytes` coerce to `&CStr`.
+impl Deref for ConstCBytes {
+    type Target = CStr;
+
+    fn deref(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+impl AsRef<CStr> for ConstCBytes {
+    fn as_ref(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+/// The bytes without the NUL terminating byte, like `to_bytes()`.
+impl AsRef<[u8]> for ConstCBytes {
+    fn as_ref(&self) -> &[u8] {
+        self.to_bytes()
+    }
+}
+
+/// Equal values borrow as equal `CStr`s, and the derived `Hash` and `Ord` of the wrapped
+/// bytes agree with those of `CStr`, since neither can contain interior NUL bytes.
+impl Borrow<CStr> for ConstCBytes {
+    fn borrow(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+impl From<ConstCBytes> for &'static CStr {
+    fn from(bytes: ConstCBytes) -> &'static CStr {
+        bytes.as_cstr()
+    }
+}
+
+/// Every `ConstCStr` is also a valid `ConstCBytes`.
+impl From<ConstCStr> for ConstCBytes {
+    fn from(cstr: ConstCStr) -> ConstCBytes {
+        ConstCBytes::from_cstr(cstr.as_cstr())
+    }
+}
+
+/// The empty C string, consisting of just the NUL terminating byte.
+impl Default for ConstCBytes {
+    fn default() -> ConstCBytes {
+        const_cstr!(b"")
+    }
+}
+
+#[test]
+fn test_creates_valid_bytes() {
+    const BYTES: ConstCBytes = const_cstr!(b"\x80\xFF");
+    const SLICE: &[u8] = b"slice";
+
+    let cstr = unsafe { CStr::from_ptr(BYTES.as_ptr()) };
+
+    assert_eq!(cstr.to_bytes(), b"\x80\xFF");
+    assert_eq!(BYTES.as_cstr(), cstr);
+    assert_eq!(const_cstr!(SLICE).to_bytes_with_nul(), b"slice\0");
+    assert_eq!(const_cstr!(b"utf-8").to_str(), Ok("utf-8"));
+    assert_eq!(format!("{:?}", const_cstr!(b"\x80a")), r#""\x80a""#);
+}
+
+#[test]
+fn test_bytes_like_cstr() {
+    use std::collections::HashSet;
+
+    const BYTES: ConstCBytes = const_cstr!(b"\x80\xFF");
+    const LEN: usize = BYTES.len();
+    const CSTR: &CStr = BYTES.as_cstr();
+
+    assert_eq!(LEN, 2);
+    assert_eq!(BYTES.len_with_nul(), 3);
+    assert!(!BYTES.is_empty() && ConstCBytes::default().is_empty());
+    assert_eq!(CSTR.to_bytes(), b"\x80\xFF");
+    assert_eq!(BYTES.count_bytes(), 2);
+    assert_eq!(BYTES.to_cstring().as_bytes(), b"\x80\xFF");
+    assert_eq!(ConstCBytes::from(const_cstr!("utf-8")).to_bytes_with_nul(), b"utf-8\0");
+
+    let set: HashSet<ConstCBytes> = [BYTES].iter().cloned().collect();
+    assert!(set.contains(CSTR));
+}
+
+#[test]
+fn test_from_bytes_with_nul_unchecked() {
+    const BYTES: ConstCBytes = unsafe { ConstCBytes::from_bytes_with_nul_unchecked(b"\x80\xFF\0") };
+
+    assert_eq!(BYTES.to_bytes(), b"\x80\xFF");
+    assert_eq!(BYTES, const_cstr!(b"\x80\xFF"));
+}
+
+#[test]
+fn test_creates_modified_utf8() {
+    const ASCII: ConstCBytes = const_mutf8!("java/lang/String");
+    const ALL: &str = "\0\u{e9}\u{20AC}\u{10400}";
+
+    assert_eq!(ASCII.to_bytes_with_nul(), b"java/lang/String\0");
+    assert_eq!(
+        const_mutf8!(ALL).to_bytes(),
+        b"\xC0\x80\xC3\xA9\xE2\x82\xAC\xED\xA0\x81\xED\xB0\x80"
+    );
+    assert_eq!(const_mutf8!("").to_bytes_with_nul(), b"\0");
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn test_cstr_escaped() {
+    use cstr_escaped;
+
+    const ALL: ConstCBytes = cstr_escaped!(r#"\a\b\f\n\r\t\v\\\'\"\?"#);
+
+    assert_eq!(ALL.to_bytes(), b"\x07\x08\x0C\n\r\t\x0B\\'\"?");
+    assert_eq!(cstr_escaped!(r"\xff\xA\x41B").to_bytes(), b"\xFF\x0AAB");
+    assert_eq!(cstr_escaped!(r"\1\12\123\1234").to_bytes(), b"\x01\x0A\x53\x534");
+    assert_eq!(cstr_escaped!("\u{e9}\\xE9").to_bytes(), b"\xC3\xA9\xE9");
+}
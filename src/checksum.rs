@@ -0,0 +1,59 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Hashes and checksums that can be computed during `const` evaluation.
+
+/// The initial state of 64-bit FNV-1a.
+pub const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Continues 64-bit FNV-1a from the state `h` over `bytes`.
+pub const fn fnv1a(mut h: u64, bytes: &[u8]) -> u64 {
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut i = 0;
+
+    while i < bytes.len() {
+        h = (h ^ bytes[i] as u64).wrapping_mul(PRIME);
+        i += 1;
+    }
+
+    h
+}
+
+/// The CRC-32 of `bytes`, as used by zlib, PNG and Ethernet.
+pub const fn crc32(bytes: &[u8]) -> u32 {
+    // The polynomial 0x04C11DB7 with its bits reversed, since this CRC is computed LSB first.
+    const POLY: u32 = 0xedb8_8320;
+
+    let mut crc = !0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        crc ^= bytes[i] as u32;
+
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ POLY } else { crc >> 1 };
+            bit += 1;
+        }
+
+        i += 1;
+    }
+
+    !crc
+}
+
+#[test]
+fn test_known_values() {
+    assert_eq!(fnv1a(FNV_OFFSET_BASIS, b""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"a"), 0xaf63_dc4c_8601_ec8c);
+    assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"foobar"), 0x8594_4171_f739_67e8);
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+}
@@ -0,0 +1,100 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Core Foundation string constants, enabled by the `cf` feature on Apple platforms.
+//!
+//! Unlike the strings of `CFSTR()`, which the C compiler lays out as `CFString` objects in
+//! the binary, these are created the first time they are used. They still never copy the
+//! string nor release the `CFString`, so each costs one small allocation for the life of the
+//! program rather than one per use.
+use std::fmt;
+use std::sync::OnceLock;
+
+use core_foundation_sys::base::{kCFAllocatorDefault, kCFAllocatorNull};
+use core_foundation_sys::string::{
+    kCFStringEncodingUTF8, CFStringCreateWithCStringNoCopy, CFStringRef,
+};
+
+use ConstCStr;
+
+/// A `CFStringRef` for a C string constant, created on first use and never released,
+/// usually declared through the `cfstr!` macro.
+///
+/// The `CFString` points to the constant itself instead of a copy.
+pub struct ConstCFString {
+    cstr: ConstCStr,
+    cell: OnceLock<StringRef>,
+}
+
+/// A `CFStringRef` that can be shared between threads, since it is immutable and never
+/// released.
+struct StringRef(CFStringRef);
+
+unsafe impl Send for StringRef {}
+unsafe impl Sync for StringRef {}
+
+impl ConstCFString {
+    /// Creates a `CFString` for `cstr` that will be created on first use.
+    pub const fn new(cstr: ConstCStr) -> ConstCFString {
+        ConstCFString { cstr, cell: OnceLock::new() }
+    }
+
+    /// Returns the `CFStringRef`, creating it first if this is the first use.
+    ///
+    /// It is never released, so it can be passed anywhere a `CFStringRef` is borrowed, or
+    /// wrapped with `CFString::wrap_under_get_rule()` from `core-foundation`.
+    ///
+    /// Panics
+    /// ------
+    /// If Core Foundation fails to create the `CFString`, as it only would if out of memory.
+    pub fn as_cfstring_ref(&self) -> CFStringRef {
+        self.cell
+            .get_or_init(|| {
+                // Safe because the string is `'static`, NUL-terminated and UTF-8, and
+                // `kCFAllocatorNull` tells Core Foundation never to free it.
+                let string = unsafe {
+                    CFStringCreateWithCStringNoCopy(
+                        kCFAllocatorDefault,
+                        self.cstr.as_ptr(),
+                        kCFStringEncodingUTF8,
+                        kCFAllocatorNull,
+                    )
+                };
+
+                assert!(!string.is_null(), "failed to create a CFString for {:?}", self.cstr);
+                StringRef(string)
+            })
+            .0
+    }
+
+    /// Returns the C string constant the `CFString` is created for.
+    pub const fn as_cstr(&self) -> ConstCStr {
+        self.cstr
+    }
+}
+
+impl fmt::Debug for ConstCFString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ConstCFString").field(&self.cstr).finish()
+    }
+}
+
+#[test]
+fn test_cfstr() {
+    use core_foundation_sys::string::CFStringGetLength;
+
+    static NAME: ConstCFString = ConstCFString::new(const_cstr!("Caf\u{e9}"));
+
+    let string = NAME.as_cfstring_ref();
+    let cached = || cfstr!("Caf\u{e9}");
+
+    assert_eq!(NAME.as_cfstring_ref(), string);
+    assert_eq!(unsafe { CFStringGetLength(string) }, 4);
+    assert_eq!(cached(), cached());
+    assert_eq!(NAME.as_cstr().to_str(), "Caf\u{e9}");
+}
@@ -0,0 +1,214 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str::Utf8Error;
+
+use ConstCStr;
+
+/// Either a `ConstCStr` or a `CString`, for APIs that take C strings which are usually
+/// constants but may be built at runtime, without allocating for the constants:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use std::ffi::CString;
+/// use const_cstr::CowCStr;
+///
+/// fn set_title<T: Into<CowCStr>>(title: T) -> CowCStr {
+///     let title = title.into();
+///     // e.g. `gtk_window_set_title(window, title.as_ptr())`
+///     title
+/// }
+/// # fn main() {
+/// assert_eq!(set_title(const_cstr!("Untitled")).to_str(), Ok("Untitled"));
+/// assert_eq!(set_title(CString::new(format!("Page {}", 2)).unwrap()).to_str(), Ok("Page 2"));
+/// # }
+/// ```
+///
+/// It hashes, compares and orders the same as `CStr`, whichever variant it is.
+#[derive(Clone)]
+pub enum CowCStr {
+    /// A C string constant.
+    Static(ConstCStr),
+    /// A C string built at runtime.
+    Owned(CString),
+}
+
+impl CowCStr {
+    /// Returns the string as a `CStr`.
+    pub fn as_cstr(&self) -> &CStr {
+        match *self {
+            CowCStr::Static(ref cstr) => cstr.as_cstr(),
+            CowCStr::Owned(ref cstring) => cstring,
+        }
+    }
+
+    /// Returns a pointer to the beginning of the string, valid for as long as `self` is.
+    pub fn as_ptr(&self) -> *const c_char {
+        self.as_cstr().as_ptr()
+    }
+
+    /// Returns the string, without the NUL terminating byte, if it is valid UTF-8.
+    ///
+    /// This never fails for a `Static` string.
+    pub fn to_str(&self) -> Result<&str, Utf8Error> {
+        match *self {
+            CowCStr::Static(ref cstr) => Ok(cstr.to_str()),
+            CowCStr::Owned(ref cstring) => cstring.to_str(),
+        }
+    }
+
+    /// Returns the string as a byte slice, **without** the NUL terminating byte.
+    pub fn to_bytes(&self) -> &[u8] {
+        self.as_cstr().to_bytes()
+    }
+
+    /// Returns the string as a byte slice, **with** the NUL terminating byte.
+    pub fn to_bytes_with_nul(&self) -> &[u8] {
+        self.as_cstr().to_bytes_with_nul()
+    }
+
+    /// Returns the length of the string in bytes, **without** the NUL terminating byte.
+    pub fn len(&self) -> usize {
+        self.to_bytes().len()
+    }
+
+    /// Returns `true` if the string has no bytes before the NUL terminating byte.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the string is a constant, which did not allocate.
+    pub fn is_static(&self) -> bool {
+        match *self {
+            CowCStr::Static(_) => true,
+            CowCStr::Owned(_) => false,
+        }
+    }
+
+    /// Converts the string into a `CString`, allocating only if it is a constant.
+    pub fn into_cstring(self) -> CString {
+        match self {
+            CowCStr::Static(cstr) => cstr.to_cstring(),
+            CowCStr::Owned(cstring) => cstring,
+        }
+    }
+}
+
+impl Deref for CowCStr {
+    type Target = CStr;
+
+    fn deref(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+impl AsRef<CStr> for CowCStr {
+    fn as_ref(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+impl Borrow<CStr> for CowCStr {
+    fn borrow(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+impl From<ConstCStr> for CowCStr {
+    fn from(cstr: ConstCStr) -> CowCStr {
+        CowCStr::Static(cstr)
+    }
+}
+
+impl From<CString> for CowCStr {
+    fn from(cstring: CString) -> CowCStr {
+        CowCStr::Owned(cstring)
+    }
+}
+
+/// Like `into_cstring()`.
+impl From<CowCStr> for CString {
+    fn from(cow: CowCStr) -> CString {
+        cow.into_cstring()
+    }
+}
+
+/// Borrows a `Static` string and moves an `Owned` one, without allocating either way.
+impl From<CowCStr> for Cow<'static, CStr> {
+    fn from(cow: CowCStr) -> Cow<'static, CStr> {
+        match cow {
+            CowCStr::Static(cstr) => Cow::Borrowed(cstr.as_cstr()),
+            CowCStr::Owned(cstring) => Cow::Owned(cstring),
+        }
+    }
+}
+
+impl fmt::Debug for CowCStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_cstr(), f)
+    }
+}
+
+impl PartialEq for CowCStr {
+    fn eq(&self, other: &CowCStr) -> bool {
+        self.as_cstr() == other.as_cstr()
+    }
+}
+
+impl Eq for CowCStr {}
+
+impl Hash for CowCStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_cstr().hash(state)
+    }
+}
+
+impl Ord for CowCStr {
+    fn cmp(&self, other: &CowCStr) -> Ordering {
+        self.as_cstr().cmp(other.as_cstr())
+    }
+}
+
+impl PartialOrd for CowCStr {
+    fn partial_cmp(&self, other: &CowCStr) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[test]
+fn test_cow_cstr() {
+    use std::collections::HashSet;
+
+    let constant = CowCStr::from(const_cstr!("Hello"));
+    let owned = CowCStr::from(CString::new("Hello").unwrap());
+
+    assert!(constant.is_static() && !owned.is_static());
+    assert_eq!(constant, owned);
+    assert_eq!(constant.as_ptr(), const_cstr!("Hello").as_ptr());
+    assert_eq!(owned.to_str(), Ok("Hello"));
+    assert_eq!(owned.to_bytes_with_nul(), b"Hello\0");
+    assert_eq!(owned.len(), 5);
+    assert_eq!(constant.count_bytes(), 5);
+    assert!(CowCStr::from(CString::new(&b"\xFF"[..]).unwrap()).to_str().is_err());
+
+    let set: HashSet<CowCStr> = vec![constant.clone()].into_iter().collect();
+    assert!(set.contains(const_cstr!("Hello").as_cstr()));
+
+    match Cow::from(constant) {
+        Cow::Borrowed(cstr) => assert_eq!(cstr.to_bytes(), b"Hello"),
+        Cow::Owned(_) => panic!("constant was copied"),
+    }
+    assert_eq!(CString::from(owned).as_bytes(), b"Hello");
+}
@@ -0,0 +1,136 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+use std::ffi::CStr;
+use std::fmt;
+use std::ptr::NonNull;
+
+use {AsPtrOrNull, ConstCStr};
+
+/// A pointer to a C string constant, with exactly the layout of a `const char*`, for the
+/// fields of `#[repr(C)]` structs mirroring C structs.
+///
+/// It can only be created from a `ConstCStr`, so a struct made of these is safe to build,
+/// even in a `static`, and always points to valid strings. `Option<ConstCStrPtr>` has the
+/// same layout too, with `None` as a null pointer:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstCStrPtr;
+///
+/// #[repr(C)]
+/// pub struct PluginInfo {
+///     name: ConstCStrPtr,
+///     description: Option<ConstCStrPtr>,
+/// }
+///
+/// #[no_mangle]
+/// pub static PLUGIN_INFO: PluginInfo = PluginInfo {
+///     name: ConstCStrPtr::new(const_cstr!("example")),
+///     description: None,
+/// };
+/// # fn main() {
+/// assert_eq!(PLUGIN_INFO.name.get().to_str(), "example");
+/// # }
+/// ```
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct ConstCStrPtr {
+    ptr: NonNull<c_char>,
+}
+
+// Safe because the pointer only points to a `'static`, immutable string.
+unsafe impl Send for ConstCStrPtr {}
+unsafe impl Sync for ConstCStrPtr {}
+
+impl ConstCStrPtr {
+    /// Points to `cstr`.
+    pub const fn new(cstr: ConstCStr) -> ConstCStrPtr {
+        ConstCStrPtr { ptr: cstr.as_non_null() }
+    }
+
+    /// Returns the pointer.
+    pub const fn as_ptr(&self) -> *const c_char {
+        self.ptr.as_ptr()
+    }
+
+    /// Returns the string pointed to, finding its length first.
+    pub fn as_cstr(&self) -> &'static CStr {
+        // Safe because the pointer came from a `ConstCStr`, so it points to a `'static`
+        // NUL-terminated string.
+        unsafe { CStr::from_ptr(self.as_ptr()) }
+    }
+
+    /// Returns the string pointed to as a `ConstCStr`, finding its length first.
+    pub fn get(&self) -> ConstCStr {
+        let bytes = self.as_cstr().to_bytes_with_nul();
+        // Safe because the bytes came from a `ConstCStr`, so they are UTF-8 and end with
+        // their only NUL byte.
+        unsafe { ConstCStr::from_str_with_nul_unchecked(::std::str::from_utf8_unchecked(bytes)) }
+    }
+}
+
+impl From<ConstCStr> for ConstCStrPtr {
+    fn from(cstr: ConstCStr) -> ConstCStrPtr {
+        ConstCStrPtr::new(cstr)
+    }
+}
+
+impl From<ConstCStrPtr> for ConstCStr {
+    fn from(ptr: ConstCStrPtr) -> ConstCStr {
+        ptr.get()
+    }
+}
+
+impl AsPtrOrNull for Option<ConstCStrPtr> {
+    fn as_ptr_or_null(&self) -> *const c_char {
+        match *self {
+            Some(ptr) => ptr.as_ptr(),
+            None => ::std::ptr::null(),
+        }
+    }
+}
+
+impl fmt::Debug for ConstCStrPtr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.get(), f)
+    }
+}
+
+#[test]
+fn test_cstr_ptr() {
+    use std::mem;
+
+    #[repr(C)]
+    struct Names {
+        first: ConstCStrPtr,
+        second: Option<ConstCStrPtr>,
+    }
+
+    static NAMES: Names = Names {
+        first: ConstCStrPtr::new(const_cstr!("first")),
+        second: None,
+    };
+
+    let hello = const_cstr!("Hello");
+    let ptr = ConstCStrPtr::from(hello);
+
+    assert_eq!(mem::size_of::<ConstCStrPtr>(), mem::size_of::<*const c_char>());
+    assert_eq!(mem::size_of::<Option<ConstCStrPtr>>(), mem::size_of::<*const c_char>());
+    assert!(unsafe { mem::transmute::<Option<ConstCStrPtr>, *const c_char>(None) }.is_null());
+
+    assert_eq!(ptr.as_ptr(), hello.as_ptr());
+    assert_eq!(ConstCStr::from(ptr), hello);
+    assert_eq!(ptr.get().as_ptr(), hello.as_ptr());
+    assert_eq!(format!("{:?}", ptr), r#""Hello""#);
+
+    assert_eq!(NAMES.first.as_cstr().to_str(), Ok("first"));
+    assert!(NAMES.second.as_ptr_or_null().is_null());
+    assert_eq!(Some(ptr).as_ptr_or_null(), hello.as_ptr());
+}
@@ -0,0 +1,50 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! `defmt::Format` impls, enabled by the `defmt` feature.
+//!
+//! The strings are sent as their contents, without the NUL terminator, like the `Display`
+//! impls. Only format strings can be interned by `defmt`, so the contents are always sent
+//! in full.
+use std::char;
+
+use defmt::{Format, Formatter};
+
+use {ConstCBytes, ConstCStr, ConstWStr, ConstWStr32};
+
+impl Format for ConstCStr {
+    fn format(&self, f: Formatter) {
+        defmt::write!(f, "{=str}", self.to_str())
+    }
+}
+
+/// Sent as a string if it is valid UTF-8, and as bytes otherwise.
+impl Format for ConstCBytes {
+    fn format(&self, f: Formatter) {
+        match self.to_str() {
+            Ok(s) => defmt::write!(f, "{=str}", s),
+            Err(_) => defmt::write!(f, "{=[u8]:x}", self.to_bytes()),
+        }
+    }
+}
+
+impl Format for ConstWStr {
+    fn format(&self, f: Formatter) {
+        for c in char::decode_utf16(self.to_wide().iter().cloned()) {
+            defmt::write!(f, "{=char}", c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        }
+    }
+}
+
+impl Format for ConstWStr32 {
+    fn format(&self, f: Formatter) {
+        for &c in self.to_wide() {
+            defmt::write!(f, "{=char}", char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER))
+        }
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+use std::ffi::CString;
+use std::fmt;
+use std::ptr;
+use std::slice;
+
+use {ConstCStr, ConstCStrArray, CowCStr, EnvVarError};
+
+/// A NULL-terminated array of pointers to `KEY=VALUE` C strings, as taken for the `envp` of
+/// `execve()` and `posix_spawn()`, built at runtime from a mix of constants and strings
+/// only known at runtime.
+///
+/// Constants, including those from `const_envp!`, are not copied:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::Envp;
+/// # fn main() {
+/// let mut envp = Envp::from(const_envp!["LANG" => "C", "TERM" => "dumb"]);
+/// envp.push("HOME", &format!("/home/{}", "nobody")).unwrap();
+/// // e.g. `libc::execve(path.as_ptr(), argv.as_ptr(), envp.as_ptr())`
+/// assert_eq!(envp.len(), 3);
+/// assert_eq!(envp.iter().last().map(|var| var.to_str()), Some(Ok("HOME=/home/nobody")));
+/// # }
+/// ```
+pub struct Envp {
+    vars: Vec<CowCStr>,
+    /// A pointer to each of `vars`, followed by a null pointer.
+    ptrs: Vec<*const c_char>,
+}
+
+// Safe because the pointers only point to the strings in `vars`, which are either
+// `'static` or owned by `self`, and are never mutated.
+unsafe impl Send for Envp {}
+unsafe impl Sync for Envp {}
+
+impl Envp {
+    /// Creates an empty environment.
+    pub fn new() -> Envp {
+        Envp { vars: Vec::new(), ptrs: vec![ptr::null()] }
+    }
+
+    /// Appends a `KEY=VALUE` constant, such as one of the strings built by `const_envp!`,
+    /// without copying it.
+    pub fn push_static(&mut self, var: ConstCStr) {
+        self.push_var(CowCStr::Static(var));
+    }
+
+    /// Appends `key=value`, copying both.
+    ///
+    /// Errors
+    /// ------
+    /// If `key` is empty or contains a `=`, as `EnvVarError::InvalidKey`, or if either
+    /// contains a NUL byte, as `EnvVarError::InteriorNul`. Its `position` is an index into the
+    /// joined `KEY=VALUE` string, not into `key` or `value`, so a NUL byte in `value` is at
+    /// `key.len() + 1` plus its index there.
+    pub fn push(&mut self, key: &str, value: &str) -> Result<(), EnvVarError> {
+        if key.is_empty() || key.contains('=') {
+            return Err(EnvVarError::InvalidKey);
+        }
+
+        let var = format!("{}={}", key, value);
+
+        if let Some(position) = var.bytes().position(|b| b == 0) {
+            return Err(EnvVarError::InteriorNul { position });
+        }
+
+        // Safe because of the above check.
+        self.push_var(CowCStr::Owned(unsafe { CString::from_vec_unchecked(var.into_bytes()) }));
+        Ok(())
+    }
+
+    fn push_var(&mut self, var: CowCStr) {
+        // The pointer stays valid as `vars` grows, since it points to either a constant or
+        // the heap buffer of a `CString`, which does not move with it.
+        self.ptrs.insert(self.vars.len(), var.as_ptr());
+        self.vars.push(var);
+    }
+
+    /// Returns a pointer to the NULL-terminated array of pointers to the variables, valid
+    /// until `self` is modified or dropped.
+    pub fn as_ptr(&self) -> *const *const c_char {
+        self.ptrs.as_ptr()
+    }
+
+    /// Returns the array of pointers to the variables, **with** the null pointer at the end.
+    pub fn as_ptrs_with_null(&self) -> &[*const c_char] {
+        &self.ptrs
+    }
+
+    /// Returns the number of variables, **without** the null pointer at the end.
+    pub fn len(&self) -> usize {
+        self.vars.len()
+    }
+
+    /// Returns `true` if there are no variables, only the null pointer at the end.
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    /// Iterates over the `KEY=VALUE` strings, in the order they were added.
+    pub fn iter(&self) -> slice::Iter<'_, CowCStr> {
+        self.vars.iter()
+    }
+}
+
+/// Copies the strings built at runtime, and points the new array at the copies.
+impl Clone for Envp {
+    fn clone(&self) -> Envp {
+        let mut envp = Envp::new();
+
+        for var in &self.vars {
+            envp.push_var(var.clone());
+        }
+
+        envp
+    }
+}
+
+impl Default for Envp {
+    fn default() -> Envp {
+        Envp::new()
+    }
+}
+
+/// Starts from the variables of `const_envp!`, without copying them.
+impl From<ConstCStrArray> for Envp {
+    fn from(array: ConstCStrArray) -> Envp {
+        let mut envp = Envp::new();
+
+        for var in array {
+            envp.push_static(var);
+        }
+
+        envp
+    }
+}
+
+impl fmt::Debug for Envp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(&self.vars).finish()
+    }
+}
+
+#[test]
+fn test_envp() {
+    use std::ffi::CStr;
+
+    static BASE: ConstCStrArray = const_envp![
+        "PATH" => "/usr/bin:/bin",
+        const_cstr!("TZ") => "UTC",
+    ];
+
+    assert_eq!(
+        BASE.iter().map(|var| var.to_str()).collect::<Vec<_>>(),
+        ["PATH=/usr/bin:/bin", "TZ=UTC"]
+    );
+    assert!(BASE.as_ptrs_with_null()[2].is_null());
+
+    let mut envp = Envp::from(BASE);
+    envp.push_static(const_cstr!("LANG=C"));
+    envp.push("USER", "root").unwrap();
+    envp.push("EMPTY", "").unwrap();
+
+    assert_eq!(envp.len(), 5);
+    assert_eq!(envp.as_ptrs_with_null()[0], BASE.as_slice()[0].as_ptr());
+    assert!(envp.as_ptrs_with_null()[5].is_null());
+
+    let vars: Vec<_> = envp
+        .as_ptrs_with_null()
+        .iter()
+        .take_while(|ptr| !ptr.is_null())
+        .map(|&ptr| unsafe { CStr::from_ptr(ptr) }.to_str().unwrap())
+        .collect();
+    assert_eq!(vars, ["PATH=/usr/bin:/bin", "TZ=UTC", "LANG=C", "USER=root", "EMPTY="]);
+
+    assert_eq!(envp.push("", "x"), Err(EnvVarError::InvalidKey));
+    assert_eq!(envp.push("A=B", "x"), Err(EnvVarError::InvalidKey));
+    assert_eq!(envp.push("A", "x\0"), Err(EnvVarError::InteriorNul { position: 3 }));
+    assert_eq!(envp.len(), 5);
+
+    let cloned = envp.clone();
+    assert_eq!(cloned.as_ptrs_with_null()[0], envp.as_ptrs_with_null()[0]);
+    assert_eq!(cloned.as_ptrs_with_null()[3], cloned.iter().nth(3).unwrap().as_ptr());
+    assert_ne!(cloned.as_ptrs_with_null()[3], envp.as_ptrs_with_null()[3]);
+
+    assert!(Envp::new().is_empty());
+    assert!(unsafe { *Envp::new().as_ptr() }.is_null());
+}
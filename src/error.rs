@@ -0,0 +1,103 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::error::Error;
+use std::fmt;
+
+/// The error returned when a `&'static str` is not a valid C string, by
+/// `ConstCStr::from_str_with_nul()` and `ConstCStr::try_from()`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FromStrWithNulError {
+    /// The string contains a NUL byte before its last byte.
+    InteriorNul {
+        /// The index of the first NUL byte.
+        position: usize,
+    },
+    /// The string does not end with a NUL byte.
+    NotNulTerminated,
+}
+
+impl fmt::Display for FromStrWithNulError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FromStrWithNulError::InteriorNul { position } => {
+                write!(f, "C string contains an interior NUL byte at index {}", position)
+            }
+            FromStrWithNulError::NotNulTerminated => f.write_str("C string is not NUL-terminated"),
+        }
+    }
+}
+
+impl Error for FromStrWithNulError {}
+
+/// The error returned by `SmallCStr::new()` and `intern()` when the string to copy contains
+/// a NUL byte, which would end the C string early.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InteriorNulError {
+    /// The index of the first NUL byte.
+    pub position: usize,
+}
+
+impl fmt::Display for InteriorNulError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "C string contains an interior NUL byte at index {}", self.position)
+    }
+}
+
+impl Error for InteriorNulError {}
+
+/// The error returned by `ConstCStr::copy_to()` when the buffer is too small for the
+/// string and its NUL terminating byte.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TooLongError {
+    /// The length of the string, **with** the NUL terminating byte.
+    pub len_with_nul: usize,
+    /// The length of the buffer.
+    pub capacity: usize,
+}
+
+impl fmt::Display for TooLongError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "C string of {} bytes with its NUL byte does not fit in a buffer of {} bytes",
+            self.len_with_nul, self.capacity
+        )
+    }
+}
+
+impl Error for TooLongError {}
+
+/// The error returned by `Envp::push()` when a variable cannot be passed to C.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EnvVarError {
+    /// The key is empty or contains a `=`.
+    InvalidKey,
+    /// The key or the value contains a NUL byte.
+    InteriorNul {
+        /// The index of the first NUL byte in the `KEY=VALUE` string.
+        position: usize,
+    },
+}
+
+impl fmt::Display for EnvVarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EnvVarError::InvalidKey => {
+                f.write_str("environment variable key is empty or contains a `=`")
+            }
+            EnvVarError::InteriorNul { position } => write!(
+                f,
+                "environment variable contains an interior NUL byte at index {}",
+                position
+            ),
+        }
+    }
+}
+
+impl Error for EnvVarError {}
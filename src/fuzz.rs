@@ -0,0 +1,75 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Generating arbitrary `ConstCStr`s, for fuzzing and property testing code that takes
+//! them, enabled by the `arbitrary` and `proptest` features.
+//!
+//! Since a `ConstCStr` always wraps a `'static` string, every generated value is leaked.
+//! That is fine for the short-lived processes these run in, but keep it in mind for fuzz
+//! targets that run for a long time.
+use ConstCStr;
+
+/// `content` with any NUL bytes removed and a NUL terminator appended, leaked.
+fn leak(mut content: String) -> ConstCStr {
+    content.retain(|c| c != '\0');
+    content.push('\0');
+
+    // Safe because the only NUL byte is the one just pushed.
+    unsafe { ConstCStr::from_str_with_nul_unchecked(Box::leak(content.into_boxed_str())) }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> ::arbitrary::Arbitrary<'a> for ConstCStr {
+    fn arbitrary(u: &mut ::arbitrary::Unstructured<'a>) -> ::arbitrary::Result<ConstCStr> {
+        let content: &str = u.arbitrary()?;
+
+        Ok(leak(content.to_owned()))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <&str as ::arbitrary::Arbitrary>::size_hint(depth)
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl ::proptest::arbitrary::Arbitrary for ConstCStr {
+    type Parameters = ();
+    type Strategy = ::proptest::strategy::Map<
+        ::proptest::collection::VecStrategy<::proptest::char::CharStrategy<'static>>,
+        fn(Vec<char>) -> ConstCStr,
+    >;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+
+        ::proptest::collection::vec(::proptest::char::any(), 0..64)
+            .prop_map(|chars| leak(chars.into_iter().collect()))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let mut u = Unstructured::new(b"ab\0c");
+    let cstr = ConstCStr::arbitrary(&mut u).unwrap();
+
+    assert!(cstr.to_str().len() <= 3);
+    assert!(!cstr.to_bytes().contains(&0));
+    assert_eq!(cstr.to_bytes_with_nul().last(), Some(&0));
+}
+
+#[cfg(all(feature = "proptest", test))]
+proptest! {
+    #[test]
+    fn test_proptest(cstr: ConstCStr) {
+        prop_assert!(!cstr.to_bytes().contains(&0));
+        prop_assert_eq!(cstr.as_cstr().to_bytes(), cstr.to_bytes());
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Option tables for `getopt_long()`, built by the `getopt_long_options!` macro.
+use std::os::raw::{c_char, c_int};
+
+/// The option takes no argument.
+pub const NO_ARGUMENT: c_int = 0;
+/// The option requires an argument.
+pub const REQUIRED_ARGUMENT: c_int = 1;
+/// The option takes an optional argument.
+pub const OPTIONAL_ARGUMENT: c_int = 2;
+
+/// A long option, laid out exactly like the `struct option` taken by `getopt_long()` and
+/// `getopt_long_only()`. A pointer to an array of these can be passed wherever a
+/// `*const libc::option` is expected.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct LongOption {
+    /// The name of the option, without the leading `--`.
+    pub name: *const c_char,
+    /// One of `NO_ARGUMENT`, `REQUIRED_ARGUMENT` or `OPTIONAL_ARGUMENT`.
+    pub has_arg: c_int,
+    /// If not null, `getopt_long()` stores `val` here and returns 0 instead of returning `val`.
+    pub flag: *mut c_int,
+    /// The value to return, or to store in `*flag`, when the option is found.
+    pub val: c_int,
+}
+
+#[test]
+fn test_getopt_long_options() {
+    use std::ffi::CStr;
+
+    const OPTIONS: &[LongOption] = getopt_long_options! {
+        "help" => no_argument, 'h';
+        "output" => required_argument, 'o';
+        "color" => optional_argument, 1000;
+    };
+
+    assert_eq!(OPTIONS.len(), 4);
+    assert_eq!(unsafe { CStr::from_ptr(OPTIONS[1].name) }.to_str(), Ok("output"));
+    assert_eq!(OPTIONS[0].has_arg, NO_ARGUMENT);
+    assert_eq!(OPTIONS[1].has_arg, REQUIRED_ARGUMENT);
+    assert_eq!(OPTIONS[2].has_arg, OPTIONAL_ARGUMENT);
+    assert_eq!(OPTIONS[0].val, 'h' as c_int);
+    assert_eq!(OPTIONS[2].val, 1000);
+    assert!(OPTIONS.iter().all(|option| option.flag.is_null()));
+
+    let end = OPTIONS[3];
+    assert!(end.name.is_null() && end.flag.is_null());
+    assert_eq!((end.has_arg, end.val), (0, 0));
+}
@@ -0,0 +1,77 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Conversions to `glib::GStr`, enabled by the `glib` feature.
+//!
+//! A `ConstCStr` is already UTF-8 and NUL-terminated, exactly what a `GStr` requires, so
+//! constants can be passed to GObject signal and property APIs without copying them or
+//! checking them again at runtime.
+use glib::value::ToValue;
+use glib::{GStr, IntoGStr, Type, Value};
+
+use ConstCStr;
+
+impl ConstCStr {
+    /// Returns the wrapped string as a `glib::GStr`, without copying or checking it.
+    ///
+    /// ```rust,ignore
+    /// # #[macro_use] extern crate const_cstr;
+    /// const_cstr! {
+    ///     SIGNAL_CLICKED = "clicked";
+    /// }
+    ///
+    /// button.emit_by_name::<()>(SIGNAL_CLICKED.as_gstr(), &[]);
+    /// ```
+    pub const fn as_gstr(&self) -> &'static GStr {
+        // Safe because the wrapped string is always UTF-8 and ends with its only NUL byte.
+        unsafe { GStr::from_utf8_with_nul_unchecked(self.to_bytes_with_nul()) }
+    }
+}
+
+impl From<ConstCStr> for &'static GStr {
+    fn from(cstr: ConstCStr) -> &'static GStr {
+        cstr.as_gstr()
+    }
+}
+
+impl AsRef<GStr> for ConstCStr {
+    fn as_ref(&self) -> &GStr {
+        self.as_gstr()
+    }
+}
+
+/// Passes the string as is, where a `&str` would be copied to add a NUL terminator.
+impl IntoGStr for ConstCStr {
+    fn run_with_gstr<T, F: FnOnce(&GStr) -> T>(self, f: F) -> T {
+        f(self.as_gstr())
+    }
+}
+
+/// A string `Value`, as for the `GStr`.
+impl ToValue for ConstCStr {
+    fn to_value(&self) -> Value {
+        self.as_gstr().to_value()
+    }
+
+    fn value_type(&self) -> Type {
+        self.as_gstr().value_type()
+    }
+}
+
+#[test]
+fn test_as_gstr() {
+    const_cstr! {
+        NAME = "notify::label";
+    }
+
+    assert_eq!(NAME.as_gstr(), "notify::label");
+    assert_eq!(NAME.as_gstr().as_ptr(), NAME.as_ptr());
+    assert_eq!(<&GStr>::from(NAME).as_str(), NAME.to_str());
+    assert!(NAME.run_with_gstr(|s| s.as_ptr() == NAME.as_ptr()));
+    assert_eq!(NAME.to_value().get::<&str>(), Ok("notify::label"));
+}
@@ -0,0 +1,73 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+
+use {ConstCStr, InteriorNulError};
+
+/// The interned strings, keyed by their contents without the NUL terminator.
+static INTERNED: Mutex<Option<HashMap<&'static str, ConstCStr>>> = Mutex::new(None);
+
+/// Returns a C string equal to `s`, stored once for the rest of the program no matter how
+/// many times the same string is interned, from any thread.
+///
+/// For C APIs that hold on to name pointers indefinitely, such as class and property
+/// registration, with names that are only known at runtime:
+///
+/// ```rust
+/// let class_name = format!("{}Widget", "Custom");
+///
+/// let interned = const_cstr::intern(&class_name).unwrap();
+/// // e.g. `g_type_register_static(parent, interned.as_ptr(), &info, 0)`
+/// assert_eq!(interned.to_str(), "CustomWidget");
+/// assert_eq!(const_cstr::intern("CustomWidget").unwrap().as_ptr(), interned.as_ptr());
+/// ```
+///
+/// Every distinct string is leaked, so only intern strings from a bounded set.
+///
+/// Errors
+/// ------
+/// If `s` contains a NUL byte.
+pub fn intern(s: &str) -> Result<ConstCStr, InteriorNulError> {
+    if let Some(position) = s.bytes().position(|b| b == 0) {
+        return Err(InteriorNulError { position });
+    }
+
+    let mut interned = INTERNED.lock().unwrap_or_else(PoisonError::into_inner);
+    let interned = interned.get_or_insert_with(HashMap::new);
+
+    if let Some(&cstr) = interned.get(s) {
+        return Ok(cstr);
+    }
+
+    let val: &'static str = Box::leak(format!("{}\0", s).into_boxed_str());
+    // Safe because of the above check.
+    let cstr = unsafe { ConstCStr::from_str_with_nul_unchecked(val) };
+
+    interned.insert(cstr.to_str(), cstr);
+    Ok(cstr)
+}
+
+#[test]
+fn test_intern() {
+    use std::thread;
+
+    let first = intern(&String::from("intern_test")).unwrap();
+    let threads: Vec<_> = (0..4)
+        .map(|_| thread::spawn(|| intern("intern_test").unwrap()))
+        .collect();
+
+    for thread in threads {
+        assert_eq!(thread.join().unwrap().as_ptr(), first.as_ptr());
+    }
+
+    assert_eq!(first.to_bytes_with_nul(), b"intern_test\0");
+    assert_ne!(intern("intern_other").unwrap().as_ptr(), first.as_ptr());
+    assert_eq!(intern("a\0b"), Err(InteriorNulError { position: 1 }));
+}
@@ -0,0 +1,111 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::ops::Deref;
+use std::str::Utf8Error;
+use std::sync::OnceLock;
+
+/// A C string computed the first time it is used, declared as a `static` by the
+/// `lazy_cstr!` macro, for strings that are almost constant but need some information
+/// only available at runtime.
+///
+/// Note
+/// ----
+/// Prefer the `lazy_cstr!` macro to create an instance of this struct
+/// over manual initialization.
+pub struct LazyCStr {
+    cell: OnceLock<CString>,
+    init: fn() -> CString,
+}
+
+impl LazyCStr {
+    /// Creates a C string that will be computed by `init` on first use.
+    pub const fn new(init: fn() -> CString) -> LazyCStr {
+        LazyCStr { cell: OnceLock::new(), init }
+    }
+
+    /// Returns the string as a `CStr`, computing it first if this is the first use.
+    pub fn as_cstr(&self) -> &CStr {
+        self.cell.get_or_init(self.init)
+    }
+
+    /// Returns a pointer to the beginning of the string, computing it first if this is the
+    /// first use.
+    ///
+    /// The pointer is valid for as long as `self` is, which for a `static` is forever.
+    pub fn as_ptr(&self) -> *const c_char {
+        self.as_cstr().as_ptr()
+    }
+
+    /// Returns the string, without the NUL terminating byte, if it is valid UTF-8.
+    pub fn to_str(&self) -> Result<&str, Utf8Error> {
+        self.as_cstr().to_str()
+    }
+
+    /// Returns the string as a byte slice, **without** the NUL terminating byte.
+    pub fn to_bytes(&self) -> &[u8] {
+        self.as_cstr().to_bytes()
+    }
+
+    /// Returns the string as a byte slice, **with** the NUL terminating byte.
+    pub fn to_bytes_with_nul(&self) -> &[u8] {
+        self.as_cstr().to_bytes_with_nul()
+    }
+}
+
+impl Deref for LazyCStr {
+    type Target = CStr;
+
+    fn deref(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+impl AsRef<CStr> for LazyCStr {
+    fn as_ref(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+impl fmt::Debug for LazyCStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_cstr(), f)
+    }
+}
+
+#[cfg(test)]
+mod test_lazy_cstr_mod {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    pub static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    lazy_cstr! {
+        pub static GREETING = {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            format!("Hello, {}!", "world")
+        };
+        /// Documented.
+        pub(crate) static BYTES = vec![0xFF_u8];
+    }
+}
+
+#[test]
+fn test_lazy_cstr() {
+    use std::sync::atomic::Ordering;
+    use self::test_lazy_cstr_mod::*;
+
+    assert_eq!(CALLS.load(Ordering::SeqCst), 0);
+    assert_eq!(GREETING.to_str(), Ok("Hello, world!"));
+    assert_eq!(unsafe { CStr::from_ptr(GREETING.as_ptr()) }, GREETING.as_cstr());
+    assert_eq!(GREETING.count_bytes(), 13);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    assert_eq!(BYTES.to_bytes_with_nul(), b"\xFF\0");
+}
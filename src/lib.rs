@@ -20,12 +20,9 @@
 //! const_cstr! {
 //!     HELLO_CSTR = "Hello, world!";
 //!
-//!     // Multiple declarations can be made with one invocation.
-//!     // GOODNIGHT_CSTR = "Goodnight, sun!";
-//!
-//!     // But only with the same visibility:
-//!     // pub GOODNIGHT_CSTR = "Goodnight, sun!";
-//!     // ^~~ Error: expected identifier, found `pub` 
+//!     // Multiple declarations can be made with one invocation,
+//!     // each with its own visibility.
+//!     pub GOODBYE_CSTR = "Goodbye, world!";
 //! }
 //!
 //! // Imagine this is an `extern "C"` function linked from some other lib.
@@ -51,42 +48,592 @@
 //! Goodnight, sun!
 //! ```
 
+#[cfg(any(feature = "derive", feature = "proc-macro"))]
+extern crate const_cstr_macros;
+
+// Lets the output of the procedural macros, which refers to `::const_cstr`, compile in tests.
+#[cfg(all(test, any(feature = "derive", feature = "proc-macro")))]
+extern crate self as const_cstr;
+
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "ash")]
+extern crate ash;
+#[cfg(all(feature = "cf", target_vendor = "apple"))]
+extern crate core_foundation_sys;
+#[cfg(feature = "defmt")]
+extern crate defmt;
+#[cfg(feature = "glib")]
+extern crate glib;
+#[cfg(feature = "libloading")]
+extern crate libloading;
+#[cfg(feature = "linkme")]
+extern crate linkme;
+#[cfg(feature = "proptest")]
+#[cfg_attr(test, macro_use)]
+extern crate proptest;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+#[cfg(feature = "subtle")]
+extern crate subtle;
+
+#[cfg(all(feature = "windows-sys", windows))]
+extern crate windows_sys;
+
 use std::os::raw::c_char;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
+use std::borrow::{Borrow, Cow};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::ptr::NonNull;
+use std::str::{Bytes, Chars};
+
+#[cfg(all(feature = "android", target_os = "android"))]
+pub use android::{LogPriority, LogTag};
+pub use array::AsPtrs;
+pub use bytes::ConstCBytes;
+#[cfg(all(feature = "cf", target_vendor = "apple"))]
+pub use core_foundation::ConstCFString;
+pub use cow::CowCStr;
+pub use cstr_ptr::ConstCStrPtr;
+pub use envp::Envp;
+pub use error::{EnvVarError, FromStrWithNulError, InteriorNulError, TooLongError};
+pub use intern::intern;
+pub use lazy::LazyCStr;
+pub use map::ConstCStrMap;
+pub use multi_sz::ConstMultiSz;
+#[cfg(all(feature = "objc", target_vendor = "apple"))]
+pub use objc_sel::ConstSel;
+pub use option::AsPtrOrNull;
+pub use ptr_array::ConstCStrArray;
+pub use registry::ConstCStrRegistry;
+pub use set::ConstCStrSet;
+pub use small::SmallCStr;
+#[cfg(all(feature = "unix", unix))]
+pub use unix::openlog;
+pub use view::CStrView;
+pub use wide::{ConstCStr16, ConstWStr, ConstWStr32};
+
+/// Derive macro generating an impl of the trait `AsCStr` for enums with unit variants.
+///
+/// Each variant is represented by a C string of its name, which can be overridden with a
+/// `#[cstr = "..."]` attribute:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::AsCStr;
+///
+/// #[derive(AsCStr)]
+/// enum Filter {
+///     Nearest,
+///     #[cstr = "linear-mipmap"]
+///     LinearMipmap,
+/// }
+/// # fn main() {
+/// assert_eq!(Filter::Nearest.as_cstr().to_str(), "Nearest");
+/// assert_eq!(Filter::LinearMipmap.as_cstr().to_str(), "linear-mipmap");
+/// # }
+/// ```
+///
+/// Requires the `derive` feature.
+#[cfg(feature = "derive")]
+pub use const_cstr_macros::AsCStr;
+
+/// Derive macro generating an impl of the trait `FromCStr` for enums with unit variants.
+///
+/// Each variant is matched by a C string of its name, which can be overridden with a
+/// `#[cstr = "..."]` attribute, so that it can be combined with `#[derive(AsCStr)]` to
+/// convert both ways:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::{AsCStr, FromCStr};
+///
+/// #[derive(AsCStr, FromCStr, Debug, PartialEq)]
+/// enum Filter {
+///     Nearest,
+///     #[cstr = "linear-mipmap"]
+///     LinearMipmap,
+/// }
+/// # fn main() {
+/// let name = Filter::LinearMipmap.as_cstr();
+///
+/// assert_eq!(Filter::from_cstr(name.as_cstr()), Some(Filter::LinearMipmap));
+/// assert_eq!(unsafe { Filter::from_ptr(name.as_ptr()) }, Some(Filter::LinearMipmap));
+/// assert_eq!(Filter::from_cstr(const_cstr!("Linear").as_cstr()), None);
+/// # }
+/// ```
+///
+/// Requires the `derive` feature.
+#[cfg(feature = "derive")]
+pub use const_cstr_macros::FromCStr;
+
+/// Create an `&'static CStr` from a string or byte string literal, checked for interior NUL
+/// bytes at compile time.
+///
+/// For when the standard `CStr` type is wanted rather than `ConstCStr`. The expansion is a
+/// constant expression, so it can initialize `const` and `static` items:
+///
+/// ```rust
+/// use std::ffi::CStr;
+/// use const_cstr::cstr;
+///
+/// const APP_NAME: &CStr = cstr!("my-app");
+///
+/// assert_eq!(APP_NAME.to_bytes_with_nul(), b"my-app\0");
+/// assert_eq!(cstr!(b"\xFF").to_bytes(), b"\xFF");
+/// ```
+///
+/// ```rust,compile_fail
+/// # use const_cstr::cstr;
+/// // error: C string contains an interior NUL byte at index 3
+/// let truncated = cstr!("foo\0bar");
+/// ```
+///
+/// Requires the `proc-macro` feature.
+#[cfg(feature = "proc-macro")]
+pub use const_cstr_macros::cstr;
+
+/// Create a `ConstCBytes` from a string literal containing C escape sequences, decoded at
+/// compile time, for byte-for-byte constants of legacy C protocols.
+///
+/// Supports every escape of C string literals: `\n`, `\t`, `\\` and the other character
+/// escapes, octal escapes of one to three digits like `\101`, and hexadecimal escapes like
+/// `\xA0`, except that the latter take at most two digits, so that each escape is exactly
+/// one byte. Use a raw string literal, so that Rust leaves the escapes for the macro:
+///
+/// ```rust
+/// use const_cstr::{cstr_escaped, ConstCBytes};
+///
+/// const GREETING: ConstCBytes = cstr_escaped!(r"\xA0\101\tOK\r\n");
+///
+/// assert_eq!(GREETING.to_bytes(), b"\xA0A\tOK\r\n");
+/// ```
+///
+/// As with `const_cstr!`, an escape producing a NUL byte fails the build:
+///
+/// ```rust,compile_fail
+/// # use const_cstr::cstr_escaped;
+/// // error: C string contains an interior NUL byte at index 3
+/// let truncated = cstr_escaped!(r"foo\0bar");
+/// ```
+///
+/// Requires the `proc-macro` feature.
+#[cfg(feature = "proc-macro")]
+pub use const_cstr_macros::cstr_escaped;
+
+#[doc(hidden)]
+pub mod __private;
 
 /// A type representing a static C-compatible string, wrapping `&'static str`.
 ///
-/// Note
-/// ----
-/// Prefer the `const_cstr!` macro to create an instance of this struct 
-/// over manual initialization. The macro will include the NUL byte for you.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// The wrapped string always ends with a NUL byte, so none of the accessors need to check
+/// for it. Create instances with the `const_cstr!` macro, which includes the NUL byte for
+/// you, or with `from_str_with_nul()` or `from_cstr()`.
+///
+/// `ConstCStr` hashes, compares and orders the same as `CStr`, so collections keyed by
+/// `ConstCStr` can be queried with a `&CStr`, such as one handed back by C:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use std::collections::HashMap;
+/// use std::ffi::CStr;
+///
+/// const_cstr! {
+///     WIDTH = "width";
+/// }
+/// # fn main() {
+/// let mut settings = HashMap::new();
+/// settings.insert(WIDTH, 80);
+///
+/// let from_c: &CStr = unsafe { CStr::from_ptr(WIDTH.as_ptr()) };
+/// assert_eq!(settings.get(from_c), Some(&80));
+/// # }
+/// ```
+///
+/// It can also be compared directly to `str`, `String`, `CStr` and `CString`, by content.
+///
+/// It does not also borrow as `str`, since `str` hashes differently than `CStr` and a type
+/// can only hash one way. To look up strings from Rust, key the collection by `to_str()`
+/// instead, or use `const_cstr_map!` or `const_cstr_set!`, whose `get_str()` and
+/// `contains_str()` methods take a `&str`.
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub struct ConstCStr {
-    /// The wrapped string value. Not intended to be used for manual initialization.
-    /// Public only to allow initialization by the `const_cstr!` macro.
-    ///
-    /// Includes the NUL terminating byte. Use `to_str()` to get an `&'static str`
-    /// without the NUL terminating byte.
-    pub val: &'static str,
+    /// Always valid UTF-8.
+    val: &'static CStr,
 }
 
 impl ConstCStr {
+    /// Wraps a `&'static CStr`, such as a `c"..."` literal, checking that it is valid UTF-8.
+    ///
+    /// ```rust,edition2021
+    /// use const_cstr::ConstCStr;
+    ///
+    /// const HELLO: ConstCStr = ConstCStr::from_cstr(c"Hello");
+    ///
+    /// assert_eq!(HELLO.to_str(), "Hello");
+    /// ```
+    ///
+    /// Panics
+    /// ------
+    /// If the string is not valid UTF-8, which fails the build if called in a `const`.
+    pub const fn from_cstr(cstr: &'static CStr) -> ConstCStr {
+        match ::std::str::from_utf8(cstr.to_bytes_with_nul()) {
+            Ok(_) => ConstCStr { val: cstr },
+            Err(err) => __private::Message::new()
+                .push_str("C string is not valid UTF-8 at index ")
+                .push_usize(err.valid_up_to())
+                .panic(),
+        }
+    }
+
+    /// Wraps a string that already ends with a NUL byte, such as one from a generated table
+    /// or another crate, checking that it is the only one.
+    ///
+    /// This is a `const fn`, so checking a constant this way fails the build on error:
+    ///
+    /// ```rust
+    /// use const_cstr::{ConstCStr, FromStrWithNulError};
+    ///
+    /// const HELLO: ConstCStr = match ConstCStr::from_str_with_nul("Hello\0") {
+    ///     Ok(cstr) => cstr,
+    ///     Err(_) => panic!("invalid C string"),
+    /// };
+    ///
+    /// assert_eq!(HELLO.to_str(), "Hello");
+    /// assert_eq!(
+    ///     ConstCStr::from_str_with_nul("Hel\0lo\0"),
+    ///     Err(FromStrWithNulError::InteriorNul { position: 3 })
+    /// );
+    /// ```
+    pub const fn from_str_with_nul(val: &'static str) -> Result<ConstCStr, FromStrWithNulError> {
+        let bytes = val.as_bytes();
+
+        if bytes.is_empty() || bytes[bytes.len() - 1] != 0 {
+            return Err(FromStrWithNulError::NotNulTerminated);
+        }
+
+        let mut i = 0;
+
+        while i < bytes.len() - 1 {
+            if bytes[i] == 0 {
+                return Err(FromStrWithNulError::InteriorNul { position: i });
+            }
+
+            i += 1;
+        }
+
+        // Safe because of the above checks.
+        Ok(unsafe { ConstCStr::from_str_with_nul_unchecked(val) })
+    }
+
+    /// Wraps a string that already ends with a NUL byte, without checking it, such as in a
+    /// hot path where the string was checked earlier.
+    ///
+    /// Safety
+    /// ------
+    /// `val` must end with a NUL byte and must not contain any other, as for
+    /// `CStr::from_bytes_with_nul_unchecked()`.
+    pub const unsafe fn from_str_with_nul_unchecked(val: &'static str) -> ConstCStr {
+        ConstCStr { val: CStr::from_bytes_with_nul_unchecked(val.as_bytes()) }
+    }
+
     /// Returns the wrapped string, without the NUL terminating byte.
     ///
     /// Compare to `CStr::to_str()` which checks that the string is valid UTF-8 first,
     /// since it starts from an arbitrary pointer instead of a Rust string slice.
-    pub fn to_str(&self) -> &'static str {
-        &self.val[..self.val.len() - 1]
+    pub const fn to_str(&self) -> &'static str {
+        // Safe because the wrapped string is always valid UTF-8.
+        unsafe { ::std::str::from_utf8_unchecked(self.val.to_bytes()) }
     }
 
     /// Returns the wrapped string as a byte slice, **without** the NUL terminating byte.
-    pub fn to_bytes(&self) -> &'static [u8] {
-        self.to_str().as_bytes()
+    pub const fn to_bytes(&self) -> &'static [u8] {
+        self.val.to_bytes()
+    }
+
+    /// Returns the length of the wrapped string in bytes, **without** the NUL terminating
+    /// byte, like `strlen()`.
+    ///
+    /// This is a `const fn`, for C APIs that take a `(ptr, len)` pair:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// const_cstr! {
+    ///     HELLO = "Hello";
+    /// }
+    ///
+    /// const HELLO_LEN: usize = HELLO.len();
+    /// # fn main() {
+    /// assert_eq!(HELLO_LEN, 5);
+    /// # }
+    /// ```
+    pub const fn len(&self) -> usize {
+        self.val.to_bytes().len()
+    }
+
+    /// Returns the length of the wrapped string in bytes, **with** the NUL terminating byte.
+    pub const fn len_with_nul(&self) -> usize {
+        self.val.to_bytes_with_nul().len()
+    }
+
+    /// Returns `true` if the wrapped string has no bytes before the NUL terminating byte.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the wrapped string starting at byte `n`, which is still NUL-terminated, such
+    /// as to strip a known prefix without allocating:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// const_cstr! {
+    ///     EXTENSION = "GL_ARB_sync";
+    /// }
+    ///
+    /// const NAME: const_cstr::ConstCStr = EXTENSION.suffix_from(3);
+    /// # fn main() {
+    /// assert_eq!(NAME.to_str(), "ARB_sync");
+    /// # }
+    /// ```
+    ///
+    /// Panics
+    /// ------
+    /// If `n` is greater than the length of the string, not counting the NUL terminating
+    /// byte, or is not on a character boundary.
+    pub const fn suffix_from(self, n: usize) -> ConstCStr {
+        let len = self.len();
+
+        if n > len {
+            __private::Message::new()
+                .push_str("suffix index ")
+                .push_usize(n)
+                .push_str(" is out of range for C string of length ")
+                .push_usize(len)
+                .panic();
+        }
+
+        // A UTF-8 continuation byte can't start a character; the NUL terminator always can.
+        if self.val.to_bytes_with_nul()[n] & 0xC0 == 0x80 {
+            __private::Message::new()
+                .push_str("suffix index ")
+                .push_usize(n)
+                .push_str(" is not a char boundary")
+                .panic();
+        }
+
+        let (_, val) = self.val.to_bytes_with_nul().split_at(n);
+        // Safe because `n` is on a character boundary and at most the length of the content.
+        ConstCStr { val: unsafe { CStr::from_bytes_with_nul_unchecked(val) } }
+    }
+
+    /// Compares the wrapped string to `other` like C's `strcmp()`, returning the sign of
+    /// `strcmp(self.as_ptr(), other.as_ptr())`.
+    ///
+    /// This is also how `ConstCStr`s are ordered among themselves, and how they compare to
+    /// `CStr`s with `<` and `>`.
+    pub fn cmp_c(&self, other: &CStr) -> Ordering {
+        self.to_bytes().cmp(other.to_bytes())
+    }
+
+    /// Returns `true` if the C string at `ptr` is equal to the wrapped string, reading no
+    /// further than the first byte that differs, so that a `const char*` handed to a
+    /// callback can be tested without finding its length first:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// use std::os::raw::c_char;
+    ///
+    /// const_cstr! {
+    ///     QUIT = "quit";
+    /// }
+    ///
+    /// extern "C" fn on_command(command: *const c_char) -> bool {
+    ///     unsafe { QUIT.eq_ptr(command) }
+    /// }
+    /// # fn main() {
+    /// assert!(on_command(const_cstr!("quit").as_ptr()));
+    /// assert!(!on_command(const_cstr!("quitter").as_ptr()));
+    /// # }
+    /// ```
+    ///
+    /// Safety
+    /// ------
+    /// `ptr` must be non-null and point to a NUL-terminated string, or to at least as many
+    /// readable bytes as `len_with_nul()`.
+    pub unsafe fn eq_ptr(&self, ptr: *const c_char) -> bool {
+        for (i, &byte) in self.to_bytes_with_nul().iter().enumerate() {
+            if *ptr.add(i) as u8 != byte {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns `true` if the first `n` bytes of the C string at `ptr` are equal to those of
+    /// the wrapped string, like C's `strncmp()` returning zero, reading at most `n` bytes and
+    /// no further than the first byte that differs.
+    ///
+    /// As with `strncmp()`, if `n` is at most `len()` only a prefix of the wrapped string is
+    /// compared, so pass the size of the buffer at `ptr` to match it exactly:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// use std::os::raw::c_char;
+    ///
+    /// const_cstr! {
+    ///     DRIVER = "e1000";
+    /// }
+    /// # fn main() {
+    /// // A fixed-size field filled in by C, which is not NUL-terminated if it is full.
+    /// let field = *b"e1000\0\xFF\xFF";
+    /// let ptr = field.as_ptr() as *const c_char;
+    ///
+    /// unsafe {
+    ///     assert!(DRIVER.eq_ptr_n(ptr, field.len()));
+    ///     assert!(!const_cstr!("e100").eq_ptr_n(ptr, field.len()));
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// Safety
+    /// ------
+    /// `ptr` must be non-null and point to at least `n` readable bytes, or to a
+    /// NUL-terminated string.
+    pub unsafe fn eq_ptr_n(&self, ptr: *const c_char, n: usize) -> bool {
+        for (i, &byte) in self.to_bytes_with_nul().iter().take(n).enumerate() {
+            if *ptr.add(i) as u8 != byte {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns `true` if `other` is equal to the wrapped string, without the NUL terminating
+    /// byte, ignoring ASCII case, like C's `strcasecmp()` returning zero:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// const_cstr! {
+    ///     CONTENT_TYPE = "Content-Type";
+    /// }
+    /// # fn main() {
+    /// assert!(CONTENT_TYPE.eq_ignore_ascii_case("content-type"));
+    /// assert!(CONTENT_TYPE.eq_ignore_ascii_case_cstr(const_cstr!("CONTENT-TYPE").as_cstr()));
+    /// # }
+    /// ```
+    pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+        self.to_bytes().eq_ignore_ascii_case(other.as_bytes())
+    }
+
+    /// Like `eq_ignore_ascii_case()`, but for a `CStr`.
+    pub fn eq_ignore_ascii_case_cstr(&self, other: &CStr) -> bool {
+        self.to_bytes().eq_ignore_ascii_case(other.to_bytes())
+    }
+
+    /// Like `eq_ignore_ascii_case()`, but for the C string at `ptr`, reading no further than
+    /// the first byte that differs like `eq_ptr()`.
+    ///
+    /// Safety
+    /// ------
+    /// `ptr` must be non-null and point to a NUL-terminated string, or to at least as many
+    /// readable bytes as `len_with_nul()`.
+    pub unsafe fn eq_ignore_ascii_case_ptr(&self, ptr: *const c_char) -> bool {
+        for (i, &byte) in self.to_bytes_with_nul().iter().enumerate() {
+            if !(*ptr.add(i) as u8).eq_ignore_ascii_case(&byte) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns `true` if the wrapped string, without the NUL terminating byte, starts with
+    /// `prefix`:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// const_cstr! {
+    ///     EXTENSION = "GL_ARB_sync";
+    /// }
+    /// # fn main() {
+    /// assert!(EXTENSION.starts_with("GL_ARB_"));
+    /// assert!(EXTENSION.ends_with("_sync"));
+    /// assert!(EXTENSION.contains("ARB"));
+    /// # }
+    /// ```
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.to_str().starts_with(prefix)
+    }
+
+    /// Returns `true` if the wrapped string, without the NUL terminating byte, ends with
+    /// `suffix`.
+    pub fn ends_with(&self, suffix: &str) -> bool {
+        self.to_str().ends_with(suffix)
+    }
+
+    /// Returns `true` if the wrapped string, without the NUL terminating byte, contains
+    /// `needle`.
+    pub fn contains(&self, needle: &str) -> bool {
+        self.to_str().contains(needle)
+    }
+
+    /// Returns the wrapped string without the NUL terminating byte and with `prefix`
+    /// removed, or `None` if it does not start with `prefix`:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// const_cstr! {
+    ///     EXTENSION = "VK_KHR_surface";
+    /// }
+    /// # fn main() {
+    /// assert_eq!(EXTENSION.strip_prefix("VK_KHR_"), Some("surface"));
+    /// assert_eq!(EXTENSION.strip_suffix("_surface"), Some("VK_KHR"));
+    /// assert_eq!(EXTENSION.strip_prefix_cstr("VK_").unwrap().to_str(), "KHR_surface");
+    /// # }
+    /// ```
+    pub fn strip_prefix(&self, prefix: &str) -> Option<&'static str> {
+        self.to_str().strip_prefix(prefix)
+    }
+
+    /// Like `strip_prefix()`, but returns a `ConstCStr` which is still NUL-terminated, like
+    /// `suffix_from()`.
+    pub fn strip_prefix_cstr(&self, prefix: &str) -> Option<ConstCStr> {
+        if self.starts_with(prefix) {
+            Some(self.suffix_from(prefix.len()))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the wrapped string without the NUL terminating byte and with `suffix`
+    /// removed, or `None` if it does not end with `suffix`.
+    pub fn strip_suffix(&self, suffix: &str) -> Option<&'static str> {
+        self.to_str().strip_suffix(suffix)
+    }
+
+    /// Returns an iterator over the bytes of the wrapped string, without the NUL terminating
+    /// byte.
+    pub fn bytes(&self) -> Bytes<'static> {
+        self.to_str().bytes()
+    }
+
+    /// Returns an iterator over the characters of the wrapped string, without the NUL
+    /// terminating byte.
+    pub fn chars(&self) -> Chars<'static> {
+        self.to_str().chars()
     }
 
     /// Returns the wrapped string as a byte slice, *with** the NUL terminating byte.
-    pub fn to_bytes_with_nul(&self) -> &'static [u8] {
-        self.val.as_bytes()
+    pub const fn to_bytes_with_nul(&self) -> &'static [u8] {
+        self.val.to_bytes_with_nul()
     }
 
     /// Returns a pointer to the beginning of the wrapped string.
@@ -94,89 +641,3380 @@ impl ConstCStr {
     /// Suitable for passing to any function that expects a C-compatible string. 
     /// Since the underlying string is guaranteed to be `'static`, 
     /// the pointer should always be valid.
+    pub const fn as_ptr(&self) -> *const c_char {
+        self.val.as_ptr()
+    }
+
+    /// Returns a pointer to the beginning of the wrapped string along with its length
+    /// **without** the NUL terminating byte, for C APIs that take both:
     ///
-    /// Panics
-    /// ------
-    /// If the wrapped string is not NUL-terminated. 
-    /// (Unlikely if you used the `const_cstr!` macro. This is just a sanity check.)
-    pub fn as_ptr(&self) -> *const c_char {
-        let bytes = self.val.as_bytes();
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// const_cstr! {
+    ///     LABEL = "shadow pass";
+    /// }
+    /// # fn main() {
+    /// let (ptr, len) = LABEL.as_ptr_and_len();
+    /// assert_eq!((ptr, len), (LABEL.as_ptr(), 11));
+    /// # }
+    /// ```
+    pub const fn as_ptr_and_len(&self) -> (*const c_char, usize) {
+        (self.as_ptr(), self.len())
+    }
 
-        assert_eq!(bytes[bytes.len() - 1], b'\0');
+    /// Returns the pointer and length of `as_ptr_and_len()` as a [`CStrView`], a
+    /// `#[repr(C)]` struct to pass by value to APIs taking a `string_view`-like struct.
+    pub const fn as_view(&self) -> CStrView {
+        CStrView { ptr: self.as_ptr(), len: self.len() }
+    }
+
+    /// Returns a pointer to the beginning of the wrapped string as `*const u8`, whatever the
+    /// signedness of `c_char` on the target.
+    pub const fn as_u8_ptr(&self) -> *const u8 {
+        self.as_ptr().cast()
+    }
+
+    /// Returns a pointer to the beginning of the wrapped string as `*const i8`, whatever the
+    /// signedness of `c_char` on the target.
+    pub const fn as_i8_ptr(&self) -> *const i8 {
+        self.as_ptr().cast()
+    }
 
-        self.val.as_bytes().as_ptr() as *const c_char
+    /// Returns the wrapped string as a `c_char` slice, **with** the NUL terminating byte, for
+    /// FFI fields that take a buffer of `c_char`s.
+    pub const fn as_c_char_slice(&self) -> &'static [c_char] {
+        // Safe because `c_char` has the same size and alignment as `u8`.
+        unsafe { ::std::slice::from_raw_parts(self.as_ptr(), self.len_with_nul()) }
     }
 
-    /// Returns the wrapped string as an `&'static CStr`, skipping the length check that
-    /// `CStr::from_ptr()` performs (since we know the length already).
+    /// Returns a pointer to the beginning of the wrapped string, as a `NonNull`, for struct
+    /// fields and APIs that encode that it can never be null.
     ///
-    /// Panics
+    /// The pointee must not be written to, even though `NonNull::as_ptr()` returns a `*mut`.
+    pub const fn as_non_null(&self) -> NonNull<c_char> {
+        // Safe because the pointer comes from a reference.
+        unsafe { NonNull::new_unchecked(self.as_ptr() as *mut c_char) }
+    }
+
+    /// Returns the wrapped string as an `&'static CStr`, which is how it is stored, so this
+    /// is free, unlike `CStr::from_ptr()` which has to find the length first.
+    pub const fn as_cstr(&self) -> &'static CStr {
+        self.val
+    }
+
+    /// Copies the wrapped string into a new `CString`, for C APIs that take ownership of
+    /// the string or modify it.
+    ///
+    /// This allocates exactly once, without checking the string for NUL bytes again.
+    pub fn to_cstring(&self) -> CString {
+        self.as_cstr().to_owned()
+    }
+
+    /// Copies the wrapped string, **with** the NUL terminating byte, to the start of `buf`,
+    /// such as a fixed-size name field of a C struct, leaving the rest of `buf` as it was.
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// use std::os::raw::c_char;
+    ///
+    /// const_cstr! {
+    ///     NAME = "eth0";
+    /// }
+    /// # fn main() {
+    /// let mut ifr_name = [0 as c_char; 16];
+    /// NAME.copy_to(&mut ifr_name).unwrap();
+    /// assert!(const_cstr!("a very long interface name").copy_to(&mut ifr_name).is_err());
+    /// # }
+    /// ```
+    ///
+    /// Errors
     /// ------
-    /// If the wrapped string is not NUL-terminated. 
-    /// (Unlikely if you used the `const_cstr!` macro. This is just a sanity check.)
-    pub fn as_cstr(&self) -> &'static CStr {
-        let bytes = self.val.as_bytes();
+    /// If `buf` is shorter than `len_with_nul()`, in which case it is not modified.
+    pub fn copy_to(&self, buf: &mut [c_char]) -> Result<(), TooLongError> {
+        if buf.len() < self.len_with_nul() {
+            return Err(TooLongError { len_with_nul: self.len_with_nul(), capacity: buf.len() });
+        }
+
+        for (dst, &src) in buf.iter_mut().zip(self.to_bytes_with_nul()) {
+            *dst = src as c_char;
+        }
+
+        Ok(())
+    }
 
-        assert_eq!(bytes[bytes.len() - 1], b'\0');
+    /// Copies as much of the wrapped string to the start of `buf` as fits while leaving room
+    /// for a NUL terminating byte, which is always written unless `buf` is empty, like BSD's
+    /// `strlcpy()`.
+    ///
+    /// Returns the number of bytes copied, **without** the NUL terminating byte, which is
+    /// less than `len()` if the string was truncated.
+    pub fn copy_to_truncated(&self, buf: &mut [c_char]) -> usize {
+        let len = match buf.len().checked_sub(1) {
+            Some(capacity) => capacity.min(self.len()),
+            None => return 0,
+        };
 
-        // This check is safe because of the above assert.
-        // Interior nuls are more of a logic error than a memory saftey issue.
-        unsafe {
-            CStr::from_bytes_with_nul_unchecked(bytes)
+        for (dst, &src) in buf.iter_mut().zip(&self.to_bytes()[..len]) {
+            *dst = src as c_char;
         }
+
+        buf[len] = 0;
+        len
+    }
+
+    /// Returns the 64-bit FNV-1a hash of the wrapped string, without the NUL terminating
+    /// byte.
+    ///
+    /// This is a `const fn`, for a stable numeric ID to register or log alongside the name
+    /// at no runtime cost:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// const_cstr! {
+    ///     EVENT_NAME = "frame_begin";
+    /// }
+    ///
+    /// const EVENT_ID: u64 = EVENT_NAME.hash_fnv1a();
+    /// # fn main() {
+    /// assert_eq!(EVENT_ID, const_cstr!("frame_begin").hash_fnv1a());
+    /// # }
+    /// ```
+    pub const fn hash_fnv1a(&self) -> u64 {
+        checksum::fnv1a(checksum::FNV_OFFSET_BASIS, self.to_bytes())
+    }
+
+    /// Returns the CRC-32 of the wrapped string, without the NUL terminating byte, as
+    /// computed by zlib's `crc32()`.
+    ///
+    /// This is a `const fn`, like `hash_fnv1a()`.
+    pub const fn crc32(&self) -> u32 {
+        checksum::crc32(self.to_bytes())
     }
 }
 
-/// Create a C-compatible string as an rvalue or a `const` binding.
-/// Appends a NUL byte to the passed string.
+/// Makes all of `CStr`'s methods available, and lets `&ConstCStr` coerce to `&CStr`:
 ///
-/// Multiple `const` declarations can be created with one invocation, but only with the same
-/// visibility (`pub` or not).
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use std::ffi::CStr;
 ///
-/// See crate root documentation for example usage.
+/// fn takes_cstr(cstr: &CStr) -> usize {
+///     cstr.count_bytes()
+/// }
 ///
-/// Note
-/// ----
-/// For logical consistency, the passed string(s) should not contain any NUL bytes.
-/// Remember that functions consuming a C-string will only see up to the first NUL byte.
-#[macro_export]
-macro_rules! const_cstr {
-    ($(pub $strname:ident = $strval:expr);+;) => (
-        $(
-            pub const $strname: $crate::ConstCStr = const_cstr!($strval);
-        )+
-    );
-    ($strval:expr) => (
-        $crate::ConstCStr { val: concat!($strval, "\0") }
-    );
-    ($($strname:ident = $strval:expr);+;) => (
-        $(
-            const $strname: $crate::ConstCStr = const_cstr!($strval);
-        )+
-    );
+/// const_cstr! {
+///     HELLO = "Hello";
+/// }
+/// # fn main() {
+/// assert_eq!(takes_cstr(&HELLO), 5);
+/// assert!(!HELLO.is_empty());
+/// # }
+/// ```
+///
+/// Methods of `ConstCStr` itself take precedence, so `to_str()` is still the infallible one.
+impl Deref for ConstCStr {
+    type Target = CStr;
+
+    fn deref(&self) -> &CStr {
+        self.as_cstr()
+    }
 }
 
-#[test]
-fn test_creates_valid_str() {
-    const_cstr! {
-        HELLO_CSTR = "Hello, world!";
+impl AsRef<CStr> for ConstCStr {
+    fn as_ref(&self) -> &CStr {
+        self.as_cstr()
     }
+}
 
-    let cstr = unsafe { CStr::from_ptr(HELLO_CSTR.as_ptr()) };
+impl From<ConstCStr> for &'static CStr {
+    fn from(cstr: ConstCStr) -> &'static CStr {
+        cstr.as_cstr()
+    }
+}
 
-    assert_eq!(HELLO_CSTR.to_str(), cstr.to_str().unwrap());
+/// The empty C string, consisting of just the NUL terminating byte.
+impl Default for ConstCStr {
+    fn default() -> ConstCStr {
+        const_cstr!("")
+    }
 }
 
-#[cfg(test)]
-mod test_creates_pub_str_mod {
-    const_cstr! {
-        pub FIRST = "first";
-        pub SECOND = "second";
+/// Like `ConstCStr::from_str_with_nul()`.
+impl TryFrom<&'static str> for ConstCStr {
+    type Error = FromStrWithNulError;
+
+    fn try_from(val: &'static str) -> Result<ConstCStr, FromStrWithNulError> {
+        ConstCStr::from_str_with_nul(val)
     }
 }
 
-#[test]
-fn test_creates_pub_str() {
-    assert_eq!(test_creates_pub_str_mod::FIRST.to_str(), "first");
-    assert_eq!(test_creates_pub_str_mod::SECOND.to_str(), "second");
+/// Borrows the string, without allocating.
+impl From<ConstCStr> for Cow<'static, CStr> {
+    fn from(cstr: ConstCStr) -> Cow<'static, CStr> {
+        Cow::Borrowed(cstr.as_cstr())
+    }
+}
+
+/// Like `to_cstring()`.
+impl From<ConstCStr> for CString {
+    fn from(cstr: ConstCStr) -> CString {
+        cstr.to_cstring()
+    }
+}
+
+/// Like `as_ptr()`, for FFI wrappers taking `impl Into<*const c_char>`.
+impl From<ConstCStr> for *const c_char {
+    fn from(cstr: ConstCStr) -> *const c_char {
+        cstr.as_ptr()
+    }
+}
+
+/// Writes the string as a quoted and escaped literal without the NUL terminating byte, like
+/// `CStr` does, except that non-ASCII characters are shown as they are rather than as bytes.
+impl fmt::Debug for ConstCStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.to_str(), f)
+    }
+}
+
+/// Writes the string without the NUL terminating byte, honoring width, fill and alignment.
+impl fmt::Display for ConstCStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.to_str())
+    }
+}
+
+/// Equal values borrow as equal `CStr`s, since neither can contain interior NUL bytes.
+impl Borrow<CStr> for ConstCStr {
+    fn borrow(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+/// Hashes the same as the `CStr` it borrows as, unlike the wrapped `&str`.
+impl Hash for ConstCStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_cstr().hash(state)
+    }
+}
+
+/// Orders strings like C's `strcmp()`: byte by byte, without the NUL terminating byte, so
+/// that a string comes before any longer string it is a prefix of.
+impl Ord for ConstCStr {
+    fn cmp(&self, other: &ConstCStr) -> Ordering {
+        self.to_bytes().cmp(other.to_bytes())
+    }
+}
+
+impl PartialOrd for ConstCStr {
+    fn partial_cmp(&self, other: &ConstCStr) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Implements `PartialEq` and `PartialOrd` both ways between `ConstCStr` and `$ty`, by
+/// comparing them as `&$view`.
+macro_rules! impl_cmp {
+    ($ty:ty => $view:ty $(, $lt:lifetime)?) => (
+        impl<$($lt)?> PartialEq<$ty> for ConstCStr {
+            fn eq(&self, other: &$ty) -> bool {
+                AsRef::<$view>::as_ref(self) == AsRef::<$view>::as_ref(other)
+            }
+        }
+
+        impl<$($lt)?> PartialEq<ConstCStr> for $ty {
+            fn eq(&self, other: &ConstCStr) -> bool {
+                AsRef::<$view>::as_ref(self) == AsRef::<$view>::as_ref(other)
+            }
+        }
+
+        impl<$($lt)?> PartialOrd<$ty> for ConstCStr {
+            fn partial_cmp(&self, other: &$ty) -> Option<Ordering> {
+                AsRef::<$view>::as_ref(self).partial_cmp(AsRef::<$view>::as_ref(other))
+            }
+        }
+
+        impl<$($lt)?> PartialOrd<ConstCStr> for $ty {
+            fn partial_cmp(&self, other: &ConstCStr) -> Option<Ordering> {
+                AsRef::<$view>::as_ref(self).partial_cmp(AsRef::<$view>::as_ref(other))
+            }
+        }
+    );
+}
+
+impl_cmp!(str => str);
+impl_cmp!(&'a str => str, 'a);
+impl_cmp!(String => str);
+impl_cmp!(CStr => CStr);
+impl_cmp!(CString => CStr);
+
+/// The string without the NUL terminating byte, like `to_str()`.
+impl AsRef<str> for ConstCStr {
+    fn as_ref(&self) -> &str {
+        self.to_str()
+    }
+}
+
+/// The bytes without the NUL terminating byte, like `to_bytes()`.
+impl AsRef<[u8]> for ConstCStr {
+    fn as_ref(&self) -> &[u8] {
+        self.to_bytes()
+    }
+}
+
+/// A type that can be represented as a static C-compatible string.
+///
+/// With the `derive` feature enabled, this can be derived for enums with unit variants.
+pub trait AsCStr {
+    /// Returns the C string representing `self`.
+    fn as_cstr(&self) -> ConstCStr;
+}
+
+/// A type that can be parsed from a C-compatible string, such as one handed back by a C
+/// callback.
+///
+/// With the `derive` feature enabled, this can be derived for enums with unit variants.
+pub trait FromCStr: Sized {
+    /// Returns the value represented by `cstr`, if there is one.
+    fn from_cstr(cstr: &CStr) -> Option<Self>;
+
+    /// Returns the value represented by the C string at `ptr`, if there is one.
+    /// A null pointer is represented by nothing and returns `None`.
+    ///
+    /// Safety
+    /// ------
+    /// `ptr` must be null or satisfy the requirements of `CStr::from_ptr()`.
+    unsafe fn from_ptr(ptr: *const c_char) -> Option<Self> {
+        if ptr.is_null() {
+            return None;
+        }
+
+        Self::from_cstr(CStr::from_ptr(ptr))
+    }
+}
+
+/// Create a C-compatible string as an rvalue or a `const` or `static` binding.
+/// Appends a NUL byte to the passed string.
+///
+/// Multiple `const` declarations can be created with one invocation, and each one may
+/// have its own visibility: private, `pub`, or restricted (`pub(crate)`, `pub(super)`,
+/// `pub(in path)`).
+///
+/// Attributes and doc comments placed before a declaration are applied to the generated
+/// `const` item, e.g. `#[cfg(windows)]`, `#[deprecated]`, `#[allow(dead_code)]` or
+/// `#[doc(hidden)]`.
+///
+/// Each generated `const` is also documented with the string it was created from, after any
+/// doc comments of its own, so rustdoc shows the actual value.
+///
+/// The string does not have to be a literal; any constant expression of type `&'static str`
+/// works, such as an existing `const` item or an invocation of `concat!()` or `env!()`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// pub const GREETING: &str = "Hello";
+///
+/// const_cstr! {
+///     pub GREETING_CSTR = GREETING;
+/// }
+/// # fn main() {
+/// assert_eq!(GREETING_CSTR.to_str(), GREETING);
+/// assert_eq!(const_cstr!(GREETING).to_bytes_with_nul(), b"Hello\0");
+/// # }
+/// ```
+///
+/// C string literals (`c"..."`) and other `&'static CStr` constants are accepted too, if they
+/// are valid UTF-8, to ease moving between the two styles:
+///
+/// ```rust,edition2021
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr! {
+///     GREETING_CSTR = c"Hello";
+/// }
+///
+/// assert_eq!(GREETING_CSTR.to_str(), "Hello");
+/// assert_eq!(const_cstr!(c"Hello"), GREETING_CSTR);
+/// ```
+///
+/// Byte strings are accepted as well, for C strings that are not valid UTF-8. In that case
+/// the rvalue form produces a [`ConstCBytes`] instead of a `ConstCStr`; declare such
+/// constants with an ordinary `const` item:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const MAGIC: const_cstr::ConstCBytes = const_cstr!(b"\x89PNG");
+/// # fn main() {
+/// assert_eq!(MAGIC.to_bytes_with_nul(), b"\x89PNG\0");
+/// # }
+/// ```
+///
+/// Naming a second constant after the first, separated by a comma, also declares a
+/// `&'static str` constant holding the same string without the NUL terminator, so that the
+/// C and Rust forms never drift apart:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr! {
+///     pub VERSION, VERSION_STR = "1.2.3";
+/// }
+/// # fn main() {
+/// assert_eq!(VERSION_STR, "1.2.3");
+/// assert_eq!(VERSION.to_str(), VERSION_STR);
+/// # }
+/// ```
+///
+/// Prefixing the string with `upper` or `lower` converts its ASCII letters to uppercase or
+/// lowercase at compile time, for C APIs that require canonical casing. Other characters
+/// are left as they are. This works in the rvalue form too, and a companion constant gets
+/// the converted string:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr! {
+///     CONTENT_TYPE, CONTENT_TYPE_STR = lower "Content-Type";
+/// }
+/// # fn main() {
+/// assert_eq!(CONTENT_TYPE_STR, "content-type");
+/// assert_eq!(const_cstr!(upper CONTENT_TYPE_STR).to_str(), "CONTENT-TYPE");
+/// # }
+/// ```
+///
+/// Prefixing the string with `dedent` strips the indentation of multi-line literals, such as
+/// embedded SQL or shader source, at compile time. A newline right after the opening quote is
+/// removed, then the leading spaces and tabs common to all lines that are not blank, and
+/// blank lines are emptied. Like `upper` and `lower`, this works in the rvalue form too:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr! {
+///     QUERY = dedent "
+///         SELECT name
+///           FROM users
+///
+///          WHERE id = ?
+///     ";
+/// }
+/// # fn main() {
+/// assert_eq!(QUERY.to_str(), "SELECT name\n  FROM users\n\n WHERE id = ?\n");
+/// # }
+/// ```
+///
+/// Options go after a colon following the name. `max_len(n)` fails the build if the string
+/// is longer than `n` bytes, not counting the NUL terminator, for C APIs with fixed-size
+/// buffers that would otherwise silently truncate it:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr! {
+///     // Thread names on Linux are limited to 16 bytes, including the NUL.
+///     WORKER_NAME: max_len(15) = "io-worker";
+/// }
+/// # fn main() {
+/// assert_eq!(WORKER_NAME.to_str(), "io-worker");
+/// # }
+/// ```
+///
+/// ```rust,compile_fail
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr! {
+///     // error: `WORKER_NAME` is 20 bytes long, more than its `max_len` of 15
+///     WORKER_NAME: max_len(15) = "background-io-worker";
+/// }
+/// # fn main() {}
+/// ```
+///
+/// `ascii` fails the build if the string contains a non-ASCII character, for C libraries
+/// that mishandle them. To apply options to every declaration in an invocation, or in a
+/// generated module, list them in an inner attribute at the start of it instead:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr! {
+///     #![ascii]
+///     HOST = "localhost";
+///     PATH: max_len(104) = "/run/app.sock";
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```rust,compile_fail
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr! {
+///     // error: `CAFE` contains the non-ASCII character `é` at index 3
+///     CAFE: ascii = "caf\u{e9}";
+/// }
+/// # fn main() {}
+/// ```
+///
+/// `static` declarations also accept `section("name")`, which places the bytes of the string
+/// in the given link section, e.g. for an embedded memory map or for tooling that extracts
+/// the strings from the built binary, and `used`, which keeps them in the binary even if
+/// nothing refers to them. Both apply to the bytes of the string, not to the `ConstCStr`
+/// pointing to them:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr! {
+///     #![ascii]
+///     # #[cfg(target_os = "linux")]
+///     pub static BUILD_ID: section(".strings"), used = "build-1234";
+/// }
+/// # fn main() {}
+/// ```
+///
+/// Declarations can be grouped into a module generated by the macro, keeping large sets of
+/// constants organized without hand-written module boilerplate. The module body accepts
+/// everything a top-level invocation does, and is a new scope like any other module:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr! {
+///     /// OpenGL string names.
+///     pub mod gl {
+///         pub VERSION = "GL_VERSION";
+///         pub RENDERER = "GL_RENDERER";
+///     }
+/// }
+/// # fn main() {
+/// assert_eq!(gl::VERSION.to_str(), "GL_VERSION");
+/// # }
+/// ```
+///
+/// Prefixing a declaration with `static` emits a `static` item instead of a `const`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr! {
+///     pub static ATOM_NAME = "WM_PROTOCOLS";
+/// }
+/// # fn main() {
+/// assert_eq!(ATOM_NAME.as_ptr(), ATOM_NAME.as_ptr());
+/// # }
+/// ```
+///
+/// Every use of a `const` is a fresh copy, so its string may end up at different addresses
+/// in different places. A `static` has a single instance, so the pointer returned by
+/// `as_ptr()` is the same at every use site; use this for C APIs that key off the
+/// pointer value rather than the string contents.
+///
+/// See crate root documentation for example usage.
+///
+/// Interior NUL bytes
+/// ------------------
+/// Functions consuming a C-string only see up to the first NUL byte, so a string containing
+/// one would be silently truncated. The passed string(s) are therefore checked at compile
+/// time, and an interior NUL fails the build with an error giving its index:
+///
+/// ```rust,compile_fail
+/// # #[macro_use] extern crate const_cstr;
+/// # fn main() {
+/// // error: C string contains an interior NUL byte at index 3
+/// let truncated = const_cstr!("foo\0bar");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! const_cstr {
+    (@with $block:tt $($(#[$attr:meta])* $vis:vis $($head:ident)+ $(, $strname_str:ident)?
+       $(: $($opt:ident $(($($optarg:tt)*))?),+)? = $strval:expr;)*) => (
+        $(
+            $crate::const_cstr!(
+                @decl $block [$(#[$attr])* $vis] [$($head)+ $(, $strname_str)?]
+                [$($(($opt $(($($optarg)*))?))+)?] ($strval)
+            );
+        )*
+    );
+    ($(@with [$($block:tt)*])? $($(#[$attr:meta])* $vis:vis $($head:ident)+ $(, $strname_str:ident)?
+       $(: $($opt:ident $(($($optarg:tt)*))?),+)? = $strval:expr;)*
+     #![$($blockopt:ident $(($($blockoptarg:tt)*))?),+ $(,)?] $($rest:tt)*) => (
+        $crate::const_cstr!(
+            @with [$($($block)*)?]
+            $($(#[$attr])* $vis $($head)+ $(, $strname_str)?
+              $(: $($opt $(($($optarg)*))?),+)? = $strval;)*
+        );
+        $crate::const_cstr!(
+            @with [$($($block)*)? $(($blockopt $(($($blockoptarg)*))?))+] $($rest)*
+        );
+    );
+    ($($(#[$attr:meta])* $vis:vis $($head:ident)+ $(, $strname_str:ident)?
+       $(: $($opt:ident $(($($optarg:tt)*))?),+)? = $strval:expr;)*) => (
+        $crate::const_cstr!(
+            @with []
+            $($(#[$attr])* $vis $($head)+ $(, $strname_str)?
+              $(: $($opt $(($($optarg)*))?),+)? = $strval;)*
+        );
+    );
+    ($(@with [$($block:tt)*])? $(#[$attr:meta])* $vis:vis mod $modname:ident { $($body:tt)* }
+     $($rest:tt)*) => (
+        $(#[$attr])*
+        $vis mod $modname {
+            $crate::const_cstr! { @with [$($($block)*)?] $($body)* }
+        }
+        $crate::const_cstr!(@with [$($($block)*)?] $($rest)*);
+    );
+    (@with $block:tt
+     $(#[$attr0:meta])* $vis0:vis $($head0:ident)+ $(, $strname_str0:ident)?
+     $(: $($opt0:ident $(($($optarg0:tt)*))?),+)? = $strval0:expr;
+     $(#[$attr1:meta])* $vis1:vis $($head1:ident)+ $(, $strname_str1:ident)?
+     $(: $($opt1:ident $(($($optarg1:tt)*))?),+)? = $strval1:expr;
+     $(#[$attr2:meta])* $vis2:vis $($head2:ident)+ $(, $strname_str2:ident)?
+     $(: $($opt2:ident $(($($optarg2:tt)*))?),+)? = $strval2:expr;
+     $(#[$attr3:meta])* $vis3:vis $($head3:ident)+ $(, $strname_str3:ident)?
+     $(: $($opt3:ident $(($($optarg3:tt)*))?),+)? = $strval3:expr;
+     $(#[$attr4:meta])* $vis4:vis $($head4:ident)+ $(, $strname_str4:ident)?
+     $(: $($opt4:ident $(($($optarg4:tt)*))?),+)? = $strval4:expr;
+     $(#[$attr5:meta])* $vis5:vis $($head5:ident)+ $(, $strname_str5:ident)?
+     $(: $($opt5:ident $(($($optarg5:tt)*))?),+)? = $strval5:expr;
+     $(#[$attr6:meta])* $vis6:vis $($head6:ident)+ $(, $strname_str6:ident)?
+     $(: $($opt6:ident $(($($optarg6:tt)*))?),+)? = $strval6:expr;
+     $(#[$attr7:meta])* $vis7:vis $($head7:ident)+ $(, $strname_str7:ident)?
+     $(: $($opt7:ident $(($($optarg7:tt)*))?),+)? = $strval7:expr;
+     $($rest:tt)*) => (
+        $crate::const_cstr!(
+            @with $block
+            $(#[$attr0])* $vis0 $($head0)+ $(, $strname_str0)?
+            $(: $($opt0 $(($($optarg0)*))?),+)? = $strval0;
+            $(#[$attr1])* $vis1 $($head1)+ $(, $strname_str1)?
+            $(: $($opt1 $(($($optarg1)*))?),+)? = $strval1;
+            $(#[$attr2])* $vis2 $($head2)+ $(, $strname_str2)?
+            $(: $($opt2 $(($($optarg2)*))?),+)? = $strval2;
+            $(#[$attr3])* $vis3 $($head3)+ $(, $strname_str3)?
+            $(: $($opt3 $(($($optarg3)*))?),+)? = $strval3;
+            $(#[$attr4])* $vis4 $($head4)+ $(, $strname_str4)?
+            $(: $($opt4 $(($($optarg4)*))?),+)? = $strval4;
+            $(#[$attr5])* $vis5 $($head5)+ $(, $strname_str5)?
+            $(: $($opt5 $(($($optarg5)*))?),+)? = $strval5;
+            $(#[$attr6])* $vis6 $($head6)+ $(, $strname_str6)?
+            $(: $($opt6 $(($($optarg6)*))?),+)? = $strval6;
+            $(#[$attr7])* $vis7 $($head7)+ $(, $strname_str7)?
+            $(: $($opt7 $(($($optarg7)*))?),+)? = $strval7;
+        );
+        $crate::const_cstr!(@with $block $($rest)*);
+    );
+    ($(@with [$($block:tt)*])? $(#[$attr:meta])* $vis:vis static $strname:ident
+     $(, $strname_str:ident)? $(: $($opt:ident $(($($optarg:tt)*))?),+)? = $($rest:tt)*) => (
+        $crate::const_cstr!(
+            @value [@with [$($($block)*)?]]
+            [$(#[$attr])* $vis static $strname $(, $strname_str)?]
+            [$($($block)*)? $($(($opt $(($($optarg)*))?))+)?]
+            $($rest)*
+        );
+    );
+    ($(@with [$($block:tt)*])? $(#[$attr:meta])* $vis:vis $strname:ident
+     $(, $strname_str:ident)? $(: $($opt:ident $(($($optarg:tt)*))?),+)? = $($rest:tt)*) => (
+        $crate::const_cstr!(
+            @value [@with [$($($block)*)?]]
+            [$(#[$attr])* $vis const $strname $(, $strname_str)?]
+            [$($($block)*)? $($(($opt $(($($optarg)*))?))+)?]
+            $($rest)*
+        );
+    );
+    (@decl [$($block:tt)*] [$($pre:tt)*] [static $strname:ident $(, $strname_str:ident)?]
+     [$($opt:tt)*] ($strval:expr)) => (
+        $crate::const_cstr!(
+            @item [$($pre)* static $strname $(, $strname_str)?] [$($block)* $($opt)*]
+            ($strval) ($strval)
+        );
+    );
+    (@decl [$($block:tt)*] [$($pre:tt)*] [$strname:ident $(, $strname_str:ident)?]
+     [$($opt:tt)*] ($strval:expr)) => (
+        $crate::const_cstr!(
+            @item [$($pre)* const $strname $(, $strname_str)?] [$($block)* $($opt)*]
+            ($strval) ($strval)
+        );
+    );
+    (@value [$($with:tt)*] $head:tt $opts:tt upper $strval:expr; $($rest:tt)*) => (
+        $crate::const_cstr!(
+            @item $head $opts (upper $strval) ($crate::const_cstr!(@upper $strval))
+        );
+        $crate::const_cstr!($($with)* $($rest)*);
+    );
+    (@value [$($with:tt)*] $head:tt $opts:tt lower $strval:expr; $($rest:tt)*) => (
+        $crate::const_cstr!(
+            @item $head $opts (lower $strval) ($crate::const_cstr!(@lower $strval))
+        );
+        $crate::const_cstr!($($with)* $($rest)*);
+    );
+    (@value [$($with:tt)*] $head:tt $opts:tt dedent $strval:expr; $($rest:tt)*) => (
+        $crate::const_cstr!(
+            @item $head $opts (dedent $strval) ($crate::const_cstr!(@dedent $strval))
+        );
+        $crate::const_cstr!($($with)* $($rest)*);
+    );
+    (@value [$($with:tt)*] $head:tt $opts:tt $strval:expr; $($rest:tt)*) => (
+        $crate::const_cstr!(@item $head $opts ($strval) ($strval));
+        $crate::const_cstr!($($with)* $($rest)*);
+    );
+    (@item [$(#[$attr:meta])* $vis:vis $kind:ident $strname:ident] [$(($($opt:tt)*))*]
+     ($($doc:tt)*) ($strval:expr)) => (
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = concat!("C string constant: `` ", stringify!($($doc)*), " ``")]
+        $vis $kind $strname: $crate::ConstCStr = {
+            const __CONST_CSTR_VALUE: &str = $crate::__private::Src($strval).as_str();
+            $($crate::const_cstr!(@check $strname __CONST_CSTR_VALUE $($opt)*);)*
+            $crate::const_cstr!(@store $kind __CONST_CSTR_VALUE [] $(($($opt)*))*)
+        };
+    );
+    (@item [$(#[$attr:meta])* $vis:vis $kind:ident $strname:ident, $strname_str:ident]
+     $opts:tt ($($doc:tt)*) ($strval:expr)) => (
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = concat!("String constant: `` ", stringify!($($doc)*), " ``")]
+        $vis const $strname_str: &'static str = $crate::__private::Src($strval).as_str();
+        $crate::const_cstr!(
+            @item [$(#[$attr])* $vis $kind $strname] $opts ($($doc)*) ($strname_str)
+        );
+    );
+    (@store $kind:ident $value:ident [$($place:tt)*] (section($section:literal)) $($opts:tt)*) => (
+        $crate::const_cstr!(@store $kind $value [$($place)* #[link_section = $section]] $($opts)*)
+    );
+    (@store $kind:ident $value:ident [$($place:tt)*] (used) $($opts:tt)*) => (
+        $crate::const_cstr!(@store $kind $value [$($place)* #[used]] $($opts)*)
+    );
+    (@store $kind:ident $value:ident $place:tt $opt:tt $($opts:tt)*) => (
+        $crate::const_cstr!(@store $kind $value $place $($opts)*)
+    );
+    (@store $kind:ident $value:ident []) => (
+        $crate::const_cstr!($value)
+    );
+    (@store static $value:ident [$($place:tt)+]) => ({
+        $($place)*
+        static __CONST_CSTR_BYTES: [u8; $value.len() + 1] =
+            $crate::__private::CBuf::concat_with_nul(&[$value]).into_bytes();
+        $crate::__private::cstr_with_nul(&__CONST_CSTR_BYTES)
+    });
+    (@store const $value:ident [$($place:tt)+]) => (
+        compile_error!("the `section` and `used` options of `const_cstr!` require a `static`")
+    );
+    (@check $strname:ident $value:ident section($section:literal)) => (());
+    (@check $strname:ident $value:ident used) => (());
+    (@check $strname:ident $value:ident ascii) => (
+        $crate::__private::check_ascii(stringify!($strname), $value)
+    );
+    (@check $strname:ident $value:ident max_len($max:expr)) => (
+        $crate::__private::check_max_len(stringify!($strname), $value, $max)
+    );
+    (@check $strname:ident $value:ident $opt:ident $($optarg:tt)*) => (
+        compile_error!(concat!("unknown `const_cstr!` option `", stringify!($opt), "`"))
+    );
+    (@upper $strval:expr) => ({
+        const __CONST_CSTR_STR: &str = $strval;
+        const __CONST_CSTR_UPPER: $crate::__private::StrBuf<{ __CONST_CSTR_STR.len() }> =
+            $crate::__private::StrBuf::ascii_upper(__CONST_CSTR_STR);
+        __CONST_CSTR_UPPER.as_str()
+    });
+    (@lower $strval:expr) => ({
+        const __CONST_CSTR_STR: &str = $strval;
+        const __CONST_CSTR_LOWER: $crate::__private::StrBuf<{ __CONST_CSTR_STR.len() }> =
+            $crate::__private::StrBuf::ascii_lower(__CONST_CSTR_STR);
+        __CONST_CSTR_LOWER.as_str()
+    });
+    (@dedent $strval:expr) => ({
+        const __CONST_CSTR_STR: &str = $strval;
+        const __CONST_CSTR_DEDENT: $crate::__private::StrBuf<
+            { $crate::__private::dedent_len(__CONST_CSTR_STR) }
+        > = $crate::__private::StrBuf::dedent(__CONST_CSTR_STR);
+        __CONST_CSTR_DEDENT.as_str()
+    });
+    (upper $strval:expr) => (
+        $crate::const_cstr!($crate::const_cstr!(@upper $strval))
+    );
+    (lower $strval:expr) => (
+        $crate::const_cstr!($crate::const_cstr!(@lower $strval))
+    );
+    (dedent $strval:expr) => (
+        $crate::const_cstr!($crate::const_cstr!(@dedent $strval))
+    );
+    ($strval:expr) => ({
+        const __CONST_CSTR_BUF: $crate::__private::CBuf<
+            { $crate::__private::Src($strval).content_len() + 1 }
+        > = $crate::__private::Src($strval).with_nul();
+        $crate::__private::Src($strval).finish(&__CONST_CSTR_BUF)
+    });
+    ($(@with $block:tt)?) => ();
+}
+
+/// Create a fixed-size `[c_char; N]` array holding a C string padded with NUL bytes, for
+/// C struct fields like `ifreq.ifr_name` or `sockaddr_un.sun_path`.
+///
+/// The string is anything accepted by `const_cstr!`. The length of the array is inferred
+/// from where it is used, or can be given first. Either way, a string that doesn't fit
+/// together with its NUL terminator fails the build:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use std::os::raw::c_char;
+///
+/// #[repr(C)]
+/// struct IfReq {
+///     ifr_name: [c_char; 16],
+///     // ...
+/// }
+///
+/// # fn main() {
+/// let req = IfReq { ifr_name: const_cstr_padded!("eth0") };
+/// let path = const_cstr_padded!(108, b"/run/app.sock");
+///
+/// assert_eq!(req.ifr_name[3], b'0' as c_char);
+/// assert!(req.ifr_name[4..].iter().all(|&c| c == 0));
+/// assert_eq!(path.len(), 108);
+/// # }
+/// ```
+///
+/// ```rust,compile_fail
+/// # #[macro_use] extern crate const_cstr;
+/// # fn main() {
+/// // error: C string needs 21 bytes including the NUL terminator, but the array only has 16
+/// let name: [std::os::raw::c_char; 16] = const_cstr_padded!("a-very-long-ifname-x");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! const_cstr_padded {
+    ($len:expr, $strval:expr) => (
+        const { $crate::__private::padded::<{ $len }>($crate::__private::Src($strval).as_bytes()) }
+    );
+    ($strval:expr) => (
+        const { $crate::__private::padded($crate::__private::Src($strval).as_bytes()) }
+    );
+}
+
+/// Declare C string `static`s exported under their own names as unmangled symbols, so that C
+/// code linked against the Rust library can refer to the very same constants, such as the
+/// names and versions both sides of a plugin ABI must agree on.
+///
+/// Each declaration gives the C type of its symbol: `[c_char]` exports the bytes themselves,
+/// like a C `const char NAME[]`, while `ConstCStrPtr` exports a pointer to them, like a C
+/// `const char *const NAME`. Each string is anything accepted by `const_cstr!`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstCStrPtr;
+/// use std::os::raw::c_char;
+///
+/// // In C: `extern const char example_plugin_abi[];`
+/// // and `extern const char *const example_plugin_name;`
+/// export_cstr! {
+///     pub static example_plugin_abi: [c_char] = "example-abi-2";
+///     pub static example_plugin_name: ConstCStrPtr = "example";
+/// }
+/// # fn main() {
+/// assert_eq!(example_plugin_abi.len(), 14);
+/// assert_eq!(example_plugin_name.get().to_str(), "example");
+/// # }
+/// ```
+///
+/// Since C symbols are often lowercase, names are not required to be uppercase. As with
+/// `#[no_mangle]` itself, every symbol name must be unique across everything linked together.
+#[macro_export]
+macro_rules! export_cstr {
+    ($(#[$attr:meta])* $vis:vis static $name:ident: [c_char] = $strval:expr; $($rest:tt)*) => (
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = concat!("Exported C string: `` ", stringify!($strval), " ``")]
+        #[no_mangle]
+        #[allow(non_upper_case_globals)]
+        $vis static $name: [
+            ::std::os::raw::c_char;
+            $crate::__private::Src($strval).content_len() + 1
+        ] = $crate::__private::padded($crate::__private::Src($strval).as_bytes());
+        $crate::export_cstr!($($rest)*);
+    );
+    ($(#[$attr:meta])* $vis:vis static $name:ident: ConstCStrPtr = $strval:expr; $($rest:tt)*) => (
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = concat!("Exported pointer to C string: `` ", stringify!($strval), " ``")]
+        #[no_mangle]
+        #[allow(non_upper_case_globals)]
+        $vis static $name: $crate::ConstCStrPtr =
+            $crate::ConstCStrPtr::new($crate::const_cstr!($strval));
+        $crate::export_cstr!($($rest)*);
+    );
+    () => ();
+}
+
+/// Declare a module of C string `static`s that share storage: each distinct string is
+/// stored once, and a string that is the end of another (like `"error"` in `"terror"`) is
+/// stored as part of it.
+///
+/// Identical literals in separate `const_cstr!` invocations are separate constants, which
+/// the compiler is free to store separately. Declaring every string in one pool and
+/// referring to its members instead guarantees a single copy, and, since they are
+/// `static`s, a single address for each one.
+///
+/// Members are declared as in `const_cstr!`, with attributes and visibility; each string is
+/// any constant expression of type `&'static str`. As with any module, expressions refer to
+/// items outside it through `super::`.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr_pool! {
+///     pub mod messages {
+///         pub ERROR = "error";
+///         pub TERROR = "terror";
+///         pub FAILURE = "error";
+///     }
+/// }
+/// # fn main() {
+/// use messages::*;
+///
+/// assert_eq!(ERROR.as_ptr(), FAILURE.as_ptr());
+/// assert_eq!(ERROR.as_ptr(), TERROR.as_ptr().wrapping_add(1));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! const_cstr_pool {
+    ($(#[$attr:meta])* $vis:vis mod $modname:ident {
+        $($(#[$member_attr:meta])* $member_vis:vis $strname:ident = $strval:expr;)*
+    }) => (
+        $(#[$attr])*
+        $vis mod $modname {
+            const __CONST_CSTR_STRS: &[&str] = &[$($strval),*];
+            const __CONST_CSTR_LAYOUT: [u8; $crate::__private::pool_len(__CONST_CSTR_STRS)] =
+                $crate::__private::pool_bytes(__CONST_CSTR_STRS);
+            static __CONST_CSTR_POOL: [u8; $crate::__private::pool_len(__CONST_CSTR_STRS)] =
+                __CONST_CSTR_LAYOUT;
+
+            $(
+                $(#[$member_attr])*
+                #[doc = ""]
+                #[doc = concat!("C string constant: `` ", stringify!($strval), " ``")]
+                $member_vis static $strname: $crate::ConstCStr = $crate::__private::pool_cstr(
+                    &__CONST_CSTR_POOL,
+                    &__CONST_CSTR_LAYOUT,
+                    $strval,
+                );
+            )*
+        }
+    );
+}
+
+/// Declare a module of C string `static`s along with a [`ConstCStrRegistry`] of them named
+/// `REGISTRY`, which recovers the canonical constant from a string handed back by C without
+/// allocating.
+///
+/// Members are declared as in `const_cstr_pool!`, and each string is anything accepted by
+/// `const_cstr!` that produces a `ConstCStr`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr_registry! {
+///     pub mod commands {
+///         pub OPEN = "open";
+///         pub CLOSE = "close";
+///     }
+/// }
+/// # fn main() {
+/// // e.g. the name passed to a callback by a C library.
+/// let name = std::ffi::CString::new("close").unwrap();
+/// let command = unsafe { commands::REGISTRY.lookup_ptr(name.as_ptr()) };
+///
+/// assert_eq!(command.map(|c| c.as_ptr()), Some(commands::CLOSE.as_ptr()));
+/// # }
+/// ```
+///
+/// A duplicate string fails the build, as with `const_cstr_map!`.
+#[macro_export]
+macro_rules! const_cstr_registry {
+    ($(#[$attr:meta])* $vis:vis mod $modname:ident {
+        $($(#[$member_attr:meta])* $member_vis:vis $strname:ident = $strval:expr;)*
+    }) => (
+        $(#[$attr])*
+        $vis mod $modname {
+            $(
+                $(#[$member_attr])*
+                #[doc = ""]
+                #[doc = concat!("C string constant: `` ", stringify!($strval), " ``")]
+                $member_vis static $strname: $crate::ConstCStr = $crate::const_cstr!($strval);
+            )*
+
+            /// The registry of the C string constants in this module.
+            pub static REGISTRY: $crate::ConstCStrRegistry = $crate::ConstCStrRegistry::from_map(
+                $crate::const_cstr_map! { $($strval => &$strname),* }
+            );
+        }
+    );
+}
+
+/// Declare distributed slices of C string constants, which `register_cstr!` adds to from
+/// any crate linked into the program, such as the names of commands or capabilities
+/// provided by plugins, for the crate declaring the slice to enumerate at startup.
+///
+/// Each declaration is a `static` with optional attributes and visibility, and the result
+/// derefs to `[ConstCStr]`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr_slice! {
+///     pub static COMMANDS;
+/// }
+///
+/// // In any crate depending on this one:
+/// register_cstr! {
+///     in COMMANDS;
+///     QUIT = "quit";
+///     HELP = "help";
+/// }
+/// # fn main() {
+/// let mut names: Vec<_> = COMMANDS.iter().map(|name| name.to_str()).collect();
+/// names.sort();
+/// assert_eq!(names, ["help", "quit"]);
+/// # }
+/// ```
+///
+/// The order of the constants in the slice is unspecified. Distributed slices are
+/// implemented by the `linkme` crate, which supports the most common platforms.
+///
+/// Requires the `linkme` feature.
+#[cfg(feature = "linkme")]
+#[macro_export]
+macro_rules! const_cstr_slice {
+    ($(#[$attr:meta])* $vis:vis static $name:ident; $($rest:tt)*) => (
+        $(#[$attr])*
+        #[$crate::__private::linkme::distributed_slice]
+        #[linkme(crate = $crate::__private::linkme)]
+        $vis static $name: [$crate::ConstCStr];
+        $crate::const_cstr_slice!($($rest)*);
+    );
+    () => ();
+}
+
+/// Declare C string `static`s that are also added to a distributed slice declared by
+/// `const_cstr_slice!`, possibly in another crate.
+///
+/// The path to the slice comes first, after `in`, followed by declarations as in
+/// `const_cstr_pool!`, with each string anything accepted by `const_cstr!` that produces a
+/// `ConstCStr`. See `const_cstr_slice!` for an example.
+///
+/// Requires the `linkme` feature.
+#[cfg(feature = "linkme")]
+#[macro_export]
+macro_rules! register_cstr {
+    (in $($slice:ident)::+; $($rest:tt)*) => (
+        $crate::register_cstr!(@decl ($($slice)::+) $($rest)*);
+    );
+    (@decl $slice:tt $(#[$attr:meta])* $vis:vis $strname:ident = $strval:expr; $($rest:tt)*) => (
+        $(#[$attr])*
+        #[$crate::__private::linkme::distributed_slice $slice]
+        #[linkme(crate = $crate::__private::linkme)]
+        $vis static $strname: $crate::ConstCStr = $crate::const_cstr!($strval);
+        $crate::register_cstr!(@decl $slice $($rest)*);
+    );
+    (@decl $slice:tt) => ();
+}
+
+/// Create a `ConstCStr` from the name of an identifier, e.g. for looking up or registering
+/// a symbol that must have the same name in Rust and C.
+///
+/// Raw identifiers are converted without their `r#` prefix.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// #[no_mangle]
+/// pub extern "C" fn plugin_init() {}
+///
+/// # fn main() {
+/// // e.g. `libc::dlsym(handle, const_cstr_ident!(plugin_init).as_ptr())`
+/// assert_eq!(const_cstr_ident!(plugin_init).to_str(), "plugin_init");
+/// assert_eq!(const_cstr_ident!(r#type).to_str(), "type");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! const_cstr_ident {
+    ($ident:ident) => (
+        $crate::const_cstr!($crate::__private::unraw(stringify!($ident)))
+    );
+}
+
+/// Create a `ConstCStr` holding the version of the package being built, from
+/// `CARGO_PKG_VERSION`, e.g. for version reporting to C libraries.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// # fn main() {
+/// assert_eq!(cstr_version!().to_str(), env!("CARGO_PKG_VERSION"));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! cstr_version {
+    () => (
+        $crate::const_cstr!(env!("CARGO_PKG_VERSION"))
+    );
+}
+
+/// Create a `ConstCStr` holding the name of the package being built, from
+/// `CARGO_PKG_NAME`, e.g. for plugin registration with C libraries.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// # fn main() {
+/// assert_eq!(cstr_pkg_name!().to_str(), env!("CARGO_PKG_NAME"));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! cstr_pkg_name {
+    () => (
+        $crate::const_cstr!(env!("CARGO_PKG_NAME"))
+    );
+}
+
+/// Create a `ConstCStr` holding the name and version of the package being built, separated
+/// by `/` as in a user agent string, or by the given string literal.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// # fn main() {
+/// // e.g. `curl_easy_setopt(handle, CURLOPT_USERAGENT, cstr_name_version!().as_ptr())`
+/// let user_agent = cstr_name_version!();
+/// let banner = cstr_name_version!(" v");
+///
+/// assert_eq!(user_agent.to_str(), format!("{}/{}", cstr_pkg_name!().to_str(), cstr_version!().to_str()));
+/// assert_eq!(banner.to_str(), format!("{} v{}", cstr_pkg_name!().to_str(), cstr_version!().to_str()));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! cstr_name_version {
+    () => (
+        $crate::cstr_name_version!("/")
+    );
+    ($sep:literal) => (
+        $crate::const_cstr!(concat!(env!("CARGO_PKG_NAME"), $sep, env!("CARGO_PKG_VERSION")))
+    );
+}
+
+/// Create a `ConstCStr` holding the location of the invocation as `file:line`, for C
+/// assertion, logging and tracing APIs.
+///
+/// For APIs that take the file and line separately, use `cstr_file!()` with `line!()`.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// # fn main() {
+/// let location = cstr_location!();
+/// // e.g. `trace(location.as_ptr(), msg)`
+/// assert_eq!(location.to_str(), format!("{}:{}", file!(), line!() - 2));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! cstr_location {
+    () => (
+        $crate::const_cstr!(concat!(file!(), ":", line!()))
+    );
+}
+
+/// Create a `ConstCStr` holding the name of the file containing the invocation, like
+/// `file!()`.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// # fn main() {
+/// // e.g. `__assert_fail(expr.as_ptr(), cstr_file!().as_ptr(), line!(), func)`
+/// assert_eq!(cstr_file!().to_str(), file!());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! cstr_file {
+    () => (
+        $crate::const_cstr!(file!())
+    );
+}
+
+/// Get a `ConstCStr` holding the path of the enclosing function, like `__func__` in C, for
+/// C logging and tracing hooks.
+///
+/// The path comes from `std::any::type_name()`, which cannot yet be called during `const`
+/// evaluation, so unlike the other macros in this crate this one does some work at
+/// runtime: the first time each invocation is reached, it allocates the C string and leaks
+/// it, so that it can be `'static`. Every later call returns the same string.
+///
+/// Closures are named after the function they are in. The exact format of the path is up
+/// to `type_name()`, so rely on it for diagnostics only.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// fn process() -> const_cstr::ConstCStr {
+///     // e.g. `trace_enter(cstr_func!().as_ptr())`
+///     cstr_func!()
+/// }
+/// # fn main() {
+/// assert!(process().to_str().ends_with("::process"));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! cstr_func {
+    () => ({
+        fn __const_cstr_func() {}
+        static __CONST_CSTR_FUNC: ::std::sync::OnceLock<$crate::ConstCStr> =
+            ::std::sync::OnceLock::new();
+        *__CONST_CSTR_FUNC.get_or_init(|| $crate::__private::func_name(__const_cstr_func))
+    });
+}
+
+/// Format a C string at runtime, like `format!()`, reusing the previous result of the same
+/// invocation if the arguments format to the same string again.
+///
+/// Returns an `Arc<CStr>`, so call `as_ptr()` on it to pass it to C, and keep it alive for as
+/// long as C may use the pointer. Each invocation caches only its most recent string, so a
+/// call site that is mostly passed the same values, like a connection address, keeps
+/// returning the same C string until they change. The arguments are still formatted on
+/// every call, to compare them with the cached string.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// fn address(host: &str, port: u16) -> std::sync::Arc<std::ffi::CStr> {
+///     // e.g. `connect_to(cstr_format!("{}:{}", host, port).as_ptr())`
+///     cstr_format!("{}:{}", host, port)
+/// }
+/// # fn main() {
+/// let first = address("localhost", 5432);
+///
+/// assert_eq!(first.to_str(), Ok("localhost:5432"));
+/// assert_eq!(address("localhost", 5432).as_ptr(), first.as_ptr());
+/// assert_eq!(address("localhost", 6543).to_str(), Ok("localhost:6543"));
+/// # }
+/// ```
+///
+/// Panics
+/// ------
+/// If the formatted string contains a NUL byte.
+#[macro_export]
+macro_rules! cstr_format {
+    ($($arg:tt)+) => ({
+        static __CONST_CSTR_FORMAT: $crate::__private::FormatCache =
+            $crate::__private::FormatCache::new();
+        __CONST_CSTR_FORMAT.get(format_args!($($arg)+))
+    });
+}
+
+/// Concatenate `ConstCStr` constants and string constants into a new `ConstCStr` at
+/// compile time.
+///
+/// Accepts a comma-separated list of constant expressions, each of which is either a
+/// `ConstCStr` (whose NUL terminator is dropped) or a `&'static str`, such as a literal.
+/// A single NUL byte is appended to the result.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr! {
+///     PREFIX = "/usr/local";
+/// }
+///
+/// const PREFIX_OPT: const_cstr::ConstCStr = const_cstr_concat!("--prefix=", PREFIX);
+/// # fn main() {
+/// assert_eq!(PREFIX_OPT.to_str(), "--prefix=/usr/local");
+/// # }
+/// ```
+///
+/// As with `const_cstr!`, a piece containing a NUL byte fails the build.
+#[macro_export]
+macro_rules! const_cstr_concat {
+    ($($piece:expr),+ $(,)?) => ({
+        const __CONST_CSTR_PIECES: &[&str] = &[$($crate::__private::Piece($piece).as_str()),+];
+        const __CONST_CSTR_BUF: $crate::__private::CBuf<
+            { $crate::__private::concat_len(__CONST_CSTR_PIECES) + 1 }
+        > = $crate::__private::CBuf::concat_with_nul(__CONST_CSTR_PIECES);
+        const __CONST_CSTR: $crate::ConstCStr = __CONST_CSTR_BUF.as_cstr();
+        __CONST_CSTR
+    });
+}
+
+/// Build a file path `ConstCStr` from segments at compile time, joining them with `\` on
+/// Windows and `/` elsewhere.
+///
+/// Each segment is anything accepted by `const_cstr_concat!`.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const CONFIG: const_cstr::ConstCStr = const_cstr_path!("config", "app", "settings.toml");
+/// # fn main() {
+/// if cfg!(windows) {
+///     assert_eq!(CONFIG.to_str(), "config\\app\\settings.toml");
+/// } else {
+///     assert_eq!(CONFIG.to_str(), "config/app/settings.toml");
+/// }
+/// # }
+/// ```
+///
+/// See `const_wstr_path!` for the wide version, for the `W` variants of Windows APIs.
+#[macro_export]
+macro_rules! const_cstr_path {
+    ($first:expr $(, $segment:expr)* $(,)?) => (
+        $crate::const_cstr_concat!($first $(, $crate::__private::PATH_SEPARATOR, $segment)*)
+    );
+}
+
+/// Build a file path `ConstWStr` from segments at compile time, joining them with `\` on
+/// Windows and `/` elsewhere.
+///
+/// This is `const_cstr_path!` encoded as UTF-16, and takes the same segments:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// # fn main() {
+/// // e.g. `CreateFileW(const_wstr_path!("C:", "logs", "app.log").as_ptr(), ...)`
+/// let path = const_wstr_path!("logs", "app.log");
+///
+/// assert_eq!(path.to_string(), const_cstr_path!("logs", "app.log").to_str());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! const_wstr_path {
+    ($($segment:expr),+ $(,)?) => (
+        $crate::const_wstr!(
+            $crate::__private::Piece($crate::const_cstr_path!($($segment),+)).as_str()
+        )
+    );
+}
+
+/// Embed the contents of a file as a `ConstCStr`, like `include_str!()`.
+///
+/// The path is resolved relative to the file containing the invocation, exactly as
+/// `include_str!()` would. As with `const_cstr!`, a file containing a NUL byte fails the
+/// build.
+///
+/// ```rust,ignore
+/// # #[macro_use] extern crate const_cstr;
+/// const VERTEX_SHADER: const_cstr::ConstCStr = include_cstr!("shaders/vertex.glsl");
+/// ```
+#[macro_export]
+macro_rules! include_cstr {
+    ($path:expr $(,)?) => (
+        $crate::const_cstr!(include_str!($path))
+    );
+}
+
+/// Define an enum whose variants each correspond to a C string.
+///
+/// Generates the enum itself, with any attributes given, plus an inherent `as_cstr()`
+/// method returning each variant's string as a `ConstCStr` and an `as_ptr()` method
+/// returning a pointer to it, suitable for C APIs that take mode or name strings.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// c_str_enum! {
+///     #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+///     pub enum OpenMode {
+///         Read = "r",
+///         Write = "w",
+///         Append = "a",
+///     }
+/// }
+/// # fn main() {
+/// assert_eq!(OpenMode::Write.as_cstr().to_str(), "w");
+/// // e.g. `libc::fopen(path, OpenMode::Append.as_ptr())`
+/// # }
+/// ```
+///
+/// The strings are checked for interior NUL bytes exactly as in `const_cstr!`.
+#[macro_export]
+macro_rules! c_str_enum {
+    ($(
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$variant_attr:meta])* $variant:ident = $strval:expr),+ $(,)?
+        }
+    )*) => ($(
+        $(#[$attr])*
+        $vis enum $name {
+            $($(#[$variant_attr])* $variant,)+
+        }
+
+        #[allow(dead_code)]
+        impl $name {
+            /// Returns the C string corresponding to this variant.
+            pub const fn as_cstr(self) -> $crate::ConstCStr {
+                match self {
+                    $($name::$variant => $crate::const_cstr!($strval),)+
+                }
+            }
+
+            /// Returns a pointer to the C string corresponding to this variant.
+            pub const fn as_ptr(self) -> *const ::std::os::raw::c_char {
+                self.as_cstr().as_ptr()
+            }
+        }
+    )*);
+}
+
+/// Build a [`ConstCStrMap`] from C string keys to values at compile time.
+///
+/// Keys are anything accepted by `const_cstr!` that produces a `ConstCStr`, and values are
+/// constant expressions of the map's value type. Use the result to initialize a `static` or
+/// `const`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstCStrMap;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Property { Width, Height }
+///
+/// static PROPERTIES: ConstCStrMap<Property> = const_cstr_map! {
+///     "width" => Property::Width,
+///     "height" => Property::Height,
+/// };
+/// # fn main() {
+/// // e.g. a property name handed to a callback by a C library.
+/// let name = const_cstr!("height").as_ptr();
+///
+/// assert_eq!(unsafe { PROPERTIES.get_ptr(name) }, Some(&Property::Height));
+/// assert_eq!(PROPERTIES.get_str("width"), Some(&Property::Width));
+/// assert_eq!(PROPERTIES.get_str("depth"), None);
+/// # }
+/// ```
+///
+/// A duplicate key fails the build:
+///
+/// ```rust,compile_fail
+/// # #[macro_use] extern crate const_cstr;
+/// static PROPERTIES: const_cstr::ConstCStrMap<u32> = const_cstr_map! {
+///     "width" => 1,
+///     "width" => 2,
+/// };
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! const_cstr_map {
+    ($($key:expr => $value:expr),* $(,)?) => ({
+        const __CONST_CSTR_KEYS: &[$crate::ConstCStr] = &[$($crate::const_cstr!($key)),*];
+        const __CONST_CSTR_INDEX: $crate::__private::PhfIndex<{ __CONST_CSTR_KEYS.len() }> =
+            $crate::__private::PhfIndex::build(__CONST_CSTR_KEYS);
+        match &[$(($crate::const_cstr!($key), $value)),*] {
+            // Safe because the index was computed from the same keys as the entries.
+            __const_cstr_entries => unsafe {
+                $crate::ConstCStrMap::from_raw_parts(
+                    __const_cstr_entries,
+                    __CONST_CSTR_INDEX.seed,
+                    &__CONST_CSTR_INDEX.disps,
+                    &__CONST_CSTR_INDEX.slots,
+                )
+            },
+        }
+    });
+}
+
+/// Build a [`ConstCStrSet`] of C strings at compile time.
+///
+/// Members are anything accepted by `const_cstr!` that produces a `ConstCStr`. Use the
+/// result to initialize a `static` or `const`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstCStrSet;
+///
+/// static WANTED_EXTENSIONS: ConstCStrSet = const_cstr_set! {
+///     "VK_KHR_swapchain",
+///     "VK_KHR_maintenance1",
+/// };
+/// # fn main() {
+/// // e.g. an extension name reported by a C library.
+/// let name = const_cstr!("VK_KHR_swapchain").as_ptr();
+///
+/// assert!(unsafe { WANTED_EXTENSIONS.contains_ptr(name) });
+/// assert!(!WANTED_EXTENSIONS.contains_str("VK_KHR_surface"));
+/// # }
+/// ```
+///
+/// A duplicate member fails the build, as with `const_cstr_map!`.
+#[macro_export]
+macro_rules! const_cstr_set {
+    ($($key:expr),* $(,)?) => (
+        $crate::ConstCStrSet::from_map($crate::const_cstr_map! { $($key => ()),* })
+    );
+}
+
+/// Build a NULL-terminated array of C string pointers, as taken by `execv()`,
+/// `posix_spawn()` and C library init functions expecting an `argv`.
+///
+/// Arguments are anything accepted by `const_cstr!`. The result is an
+/// `&'static [*const c_char]` whose last element is a null pointer, so the `argc` to go
+/// with it is one less than its length. Since raw pointers are not `Sync`, store it in a
+/// `const` rather than a `static`; the strings it points to are `'static` either way.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use std::os::raw::c_char;
+///
+/// const ARGV: &[*const c_char] = const_argv!("ls", "-l", b"--color=never");
+/// # fn main() {
+/// assert_eq!(ARGV.len(), 4);
+/// assert!(ARGV[3].is_null());
+/// // e.g. `libc::execv(ARGV[0], ARGV.as_ptr())`
+/// # }
+/// ```
+#[macro_export]
+macro_rules! const_argv {
+    ($($arg:expr),* $(,)?) => ({
+        const __CONST_CSTR_ARGV: &[*const ::std::os::raw::c_char] = &[
+            $($crate::__private::Ptr($crate::const_cstr!($arg)).as_ptr(),)*
+            ::std::ptr::null(),
+        ];
+        __CONST_CSTR_ARGV
+    });
+}
+
+/// Build a table of C structs that pair a name with a value and end with an all-zero
+/// sentinel entry, like the `luaL_Reg` and `PyMethodDef` arrays registered with Lua and
+/// Python.
+///
+/// The first line names the struct, then its C string field and, optionally, the field
+/// to set from each entry's value. Names are anything accepted by `const_cstr!`. Any other
+/// fields, and every field of the sentinel, are zeroed. As with `const_argv!`, the result
+/// is an `&'static [T]` to be stored in a `const`, since such structs usually contain raw
+/// pointers:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use std::os::raw::{c_char, c_int};
+///
+/// # #[allow(non_camel_case_types)] type lua_State = ();
+/// #[allow(non_camel_case_types)]
+/// #[repr(C)]
+/// pub struct luaL_Reg {
+///     name: *const c_char,
+///     func: Option<unsafe extern "C" fn(*mut lua_State) -> c_int>,
+/// }
+///
+/// unsafe extern "C" fn l_answer(_: *mut lua_State) -> c_int { 42 }
+///
+/// const FUNCS: &[luaL_Reg] = const_cstr_table! {
+///     luaL_Reg { name, func };
+///     "answer" => Some(l_answer),
+/// };
+/// # fn main() {
+/// assert_eq!(FUNCS.len(), 2);
+/// assert!(FUNCS[1].name.is_null() && FUNCS[1].func.is_none());
+/// // e.g. `luaL_setfuncs(L, FUNCS.as_ptr(), 0)`
+/// # }
+/// ```
+///
+/// When the struct has more fields to set, name only the C string field and give each
+/// entry's fields in braces instead:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// # use std::os::raw::{c_char, c_int};
+/// # #[repr(C)]
+/// # pub struct PyMethodDef {
+/// #     ml_name: *const c_char,
+/// #     ml_meth: Option<unsafe extern "C" fn()>,
+/// #     ml_flags: c_int,
+/// #     ml_doc: *const c_char,
+/// # }
+/// # const METH_NOARGS: c_int = 0x0004;
+/// # unsafe extern "C" fn spam() {}
+/// const METHODS: &[PyMethodDef] = const_cstr_table! {
+///     PyMethodDef { ml_name };
+///     "spam" => {
+///         ml_meth: Some(spam),
+///         ml_flags: METH_NOARGS,
+///     },
+/// };
+/// # fn main() {
+/// assert_eq!(METHODS[0].ml_flags, METH_NOARGS);
+/// assert!(METHODS[0].ml_doc.is_null());
+/// assert!(METHODS[1].ml_name.is_null());
+/// # }
+/// ```
+///
+/// A struct for which all-zero bytes are not a valid value, e.g. one with a non-`Option`
+/// function pointer field, fails the build.
+#[macro_export]
+macro_rules! const_cstr_table {
+    ($ty:path { $name_field:ident, $value_field:ident $(,)? }; $($name:expr => $value:expr),* $(,)?) => (
+        $crate::const_cstr_table! {
+            $ty { $name_field }; $($name => { $value_field: $value }),*
+        }
+    );
+    ($ty:path { $name_field:ident $(,)? }; $($name:expr => { $($field:ident: $fval:expr),* $(,)? }),* $(,)?) => ({
+        type __ConstCStrEntry = $ty;
+        #[allow(clippy::needless_update)]
+        const __CONST_CSTR_TABLE: &[$ty] = &[
+            $(__ConstCStrEntry {
+                $name_field: $crate::__private::Ptr($crate::const_cstr!($name)).as_ptr(),
+                $($field: $fval,)*
+                ..$crate::__private::Zeroed::<$ty>::VALUE
+            },)*
+            $crate::__private::Zeroed::<$ty>::VALUE,
+        ];
+        __CONST_CSTR_TABLE
+    });
+}
+
+/// Build the option table for `getopt_long()`, terminated by the required all-zero entry.
+///
+/// Each option is its name, which is anything accepted by `const_cstr!`, then whether it
+/// takes an argument (`no_argument`, `required_argument` or `optional_argument`, as in C)
+/// and the value `getopt_long()` returns when it is found, typically the matching short
+/// option character. The result is an `&'static [getopt::LongOption]`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::getopt::LongOption;
+///
+/// const LONG_OPTIONS: &[LongOption] = getopt_long_options! {
+///     "help" => no_argument, 'h';
+///     "output" => required_argument, 'o';
+/// };
+/// # fn main() {
+/// // e.g. `libc::getopt_long(argc, argv, optstring, LONG_OPTIONS.as_ptr().cast(), ptr::null_mut())`
+/// assert_eq!(LONG_OPTIONS[1].val, 'o' as i32);
+/// assert!(LONG_OPTIONS[2].name.is_null());
+/// # }
+/// ```
+///
+/// `flag` is always null, since pointing it at a variable requires a `static mut`; fill it
+/// in on a copy of the table if needed.
+#[macro_export]
+macro_rules! getopt_long_options {
+    ($($name:expr => $has_arg:ident, $val:expr);* $(;)?) => (
+        $crate::const_cstr_table! {
+            $crate::getopt::LongOption { name };
+            $($name => {
+                has_arg: $crate::getopt_long_options!(@has_arg $has_arg),
+                val: $val as ::std::os::raw::c_int,
+            }),*
+        }
+    );
+    (@has_arg no_argument) => ($crate::getopt::NO_ARGUMENT);
+    (@has_arg required_argument) => ($crate::getopt::REQUIRED_ARGUMENT);
+    (@has_arg optional_argument) => ($crate::getopt::OPTIONAL_ARGUMENT);
+}
+
+/// Declare arrays of `ConstCStr` from lists of strings, for C APIs that take lists of
+/// names, like the layers and extensions passed to Vulkan.
+///
+/// Each declaration is a `static` or `const` with optional attributes and visibility; its
+/// type is `[ConstCStr; N]` for the number of strings given. Each string is anything
+/// accepted by `const_cstr!` that produces a `ConstCStr`. The `AsPtrs` trait then gives the
+/// array of pointers to pass to C:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::AsPtrs;
+///
+/// const_cstr_array! {
+///     pub static DEVICE_EXTENSIONS = ["VK_KHR_swapchain", "VK_KHR_maintenance1"];
+/// }
+/// # fn main() {
+/// let ptrs = DEVICE_EXTENSIONS.as_ptrs();
+/// // e.g. `info.enabledExtensionCount = ptrs.len() as u32;`
+/// // `info.ppEnabledExtensionNames = ptrs.as_ptr();`
+/// assert_eq!(ptrs.len(), 2);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! const_cstr_array {
+    ($(#[$attr:meta])* $vis:vis static $name:ident = [$($strval:expr),* $(,)?]; $($rest:tt)*) => (
+        $(#[$attr])*
+        $vis static $name: [$crate::ConstCStr; <[&str]>::len(&[$(stringify!($strval)),*])] =
+            [$($crate::const_cstr!($strval)),*];
+        $crate::const_cstr_array!($($rest)*);
+    );
+    ($(#[$attr:meta])* $vis:vis const $name:ident = [$($strval:expr),* $(,)?]; $($rest:tt)*) => (
+        $(#[$attr])*
+        $vis const $name: [$crate::ConstCStr; <[&str]>::len(&[$(stringify!($strval)),*])] =
+            [$($crate::const_cstr!($strval)),*];
+        $crate::const_cstr_array!($($rest)*);
+    );
+    () => ();
+}
+
+/// Build a [`ConstCStrArray`] at compile time: a list of C strings along with the
+/// NULL-terminated array of pointers to them that `execv()` and similar APIs take.
+///
+/// Each string is anything accepted by `const_cstr!` that produces a `ConstCStr`. Unlike
+/// `const_argv!`, the result can initialize a `static`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstCStrArray;
+///
+/// static LAYERS: ConstCStrArray = const_cstr_ptr_array![
+///     "VK_LAYER_KHRONOS_validation",
+///     "VK_LAYER_LUNARG_api_dump",
+/// ];
+/// # fn main() {
+/// // e.g. `info.enabledLayerCount = LAYERS.len() as u32;`
+/// // `info.ppEnabledLayerNames = LAYERS.as_ptr();`
+/// assert_eq!(LAYERS.len(), 2);
+/// assert!(LAYERS.as_ptrs_with_null()[2].is_null());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! const_cstr_ptr_array {
+    (@strs $($cstr:expr),*) => ({
+        const __CONST_CSTR_STRS: &[$crate::ConstCStr] = &[$($cstr),*];
+        const __CONST_CSTR_PTRS: [*const ::std::os::raw::c_char; __CONST_CSTR_STRS.len() + 1] =
+            $crate::__private::ptrs_with_null(__CONST_CSTR_STRS);
+        // Safe because `ptrs_with_null()` points to each of the strings, then null.
+        const __CONST_CSTR_ARRAY: $crate::ConstCStrArray = unsafe {
+            $crate::ConstCStrArray::from_raw_parts(__CONST_CSTR_STRS, &__CONST_CSTR_PTRS)
+        };
+        __CONST_CSTR_ARRAY
+    });
+    ($($strval:expr),* $(,)?) => (
+        $crate::const_cstr_ptr_array!(@strs $($crate::const_cstr!($strval)),*)
+    );
+}
+
+/// Build a [`ConstMultiSz`] at compile time: a list of NUL-terminated strings ending in an
+/// extra NUL byte, as stored in `REG_MULTI_SZ` registry values and taken by several Win32
+/// APIs.
+///
+/// Each string is anything accepted by `const_cstr_concat!`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstMultiSz;
+///
+/// static DEPENDENCIES: ConstMultiSz = const_multi_sz!["Tcpip", "Dnscache"];
+/// # fn main() {
+/// // e.g. `CreateServiceA(..., DEPENDENCIES.as_ptr(), ...)`
+/// assert_eq!(DEPENDENCIES.as_bytes_with_nuls(), b"Tcpip\0Dnscache\0\0");
+/// assert_eq!(DEPENDENCIES.entries().count(), 2);
+/// # }
+/// ```
+///
+/// An empty string, or one containing a NUL byte, would end the list early, and so fails
+/// the build:
+///
+/// ```rust,compile_fail
+/// # #[macro_use] extern crate const_cstr;
+/// // error: MULTI_SZ entry 1 is empty
+/// const PATHS: const_cstr::ConstMultiSz = const_multi_sz!["C:\\", ""];
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! const_multi_sz {
+    ($($strval:expr),* $(,)?) => ({
+        const __CONST_CSTR_STRS: &[&str] = &[$($crate::__private::Piece($strval).as_str()),*];
+        const __CONST_CSTR_BYTES: [u8; $crate::__private::multi_sz_len(__CONST_CSTR_STRS)] =
+            $crate::__private::multi_sz_bytes(__CONST_CSTR_STRS);
+        const __CONST_CSTR_MULTI_SZ: $crate::ConstMultiSz =
+            $crate::__private::multi_sz(&__CONST_CSTR_BYTES);
+        __CONST_CSTR_MULTI_SZ
+    });
+}
+
+/// Build the filter string of a Win32 file dialog at compile time, as the `lpstrFilter` of
+/// `OPENFILENAMEA` expects: alternating descriptions and patterns, each followed by a NUL
+/// byte, then an extra NUL.
+///
+/// Takes `description => pattern` pairs, each side anything accepted by
+/// `const_cstr_concat!`, and produces a [`ConstMultiSz`] whose entries alternate in the same
+/// way:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstMultiSz;
+///
+/// const FILTER: ConstMultiSz = const_filter_str![
+///     "Text Files (*.txt)" => "*.txt",
+///     "All Files" => "*.*",
+/// ];
+/// # fn main() {
+/// // e.g. `ofn.lpstrFilter = FILTER.as_ptr();`
+/// assert_eq!(FILTER.as_str_with_nuls(), "Text Files (*.txt)\0*.txt\0All Files\0*.*\0\0");
+/// # }
+/// ```
+///
+/// See `const_filter_wstr!` for the wide version, for `OPENFILENAMEW`.
+#[macro_export]
+macro_rules! const_filter_str {
+    ($($description:expr => $pattern:expr),* $(,)?) => (
+        $crate::const_multi_sz![$($description, $pattern),*]
+    );
+}
+
+/// Build the filter string of a Win32 file dialog at compile time, encoded as UTF-16 for the
+/// `lpstrFilter` of `OPENFILENAMEW`.
+///
+/// This is `const_filter_str!` encoded as UTF-16, and takes the same pairs. The result is
+/// an `&'static [u16]` holding every NUL, including the final extra one:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const FILTER: &[u16] = const_filter_wstr!["Images" => "*.png;*.jpg"];
+/// # fn main() {
+/// // e.g. `ofn.lpstrFilter = FILTER.as_ptr();`
+/// assert_eq!(FILTER.len(), "Images\0*.png;*.jpg\0\0".len());
+/// assert_eq!(FILTER[FILTER.len() - 2..], [0, 0]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! const_filter_wstr {
+    ($($description:expr => $pattern:expr),* $(,)?) => ({
+        const __CONST_CSTR_STR: &str =
+            $crate::const_filter_str![$($description => $pattern),*].as_str_with_nuls();
+        const __CONST_CSTR_BUF: $crate::__private::WBuf<
+            { $crate::__private::utf16_len(__CONST_CSTR_STR) }
+        > = $crate::__private::WBuf::encode_with_nuls(__CONST_CSTR_STR);
+        __CONST_CSTR_BUF.as_wide()
+    });
+}
+
+/// Build a [`ConstCStrArray`] of `KEY=VALUE` C strings at compile time, along with the
+/// NULL-terminated array of pointers to them taken for the `envp` of `execve()` and
+/// `posix_spawn()`.
+///
+/// Takes `key => value` pairs, each side anything accepted by `const_cstr_concat!`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstCStrArray;
+///
+/// static ENVP: ConstCStrArray = const_envp![
+///     "PATH" => "/usr/bin:/bin",
+///     "LC_ALL" => "C",
+/// ];
+/// # fn main() {
+/// // e.g. `libc::execve(path.as_ptr(), argv.as_ptr(), ENVP.as_ptr())`
+/// assert_eq!(ENVP.as_slice()[1].to_str(), "LC_ALL=C");
+/// assert!(ENVP.as_ptrs_with_null()[2].is_null());
+/// # }
+/// ```
+///
+/// See [`Envp`] to add variables only known at runtime. An empty key, or one containing a
+/// `=`, fails the build:
+///
+/// ```rust,compile_fail
+/// # #[macro_use] extern crate const_cstr;
+/// // error: environment variable key `A=B` contains a `=` at index 1
+/// const ENVP: const_cstr::ConstCStrArray = const_envp!["A=B" => "C"];
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! const_envp {
+    ($($key:expr => $value:expr),* $(,)?) => (
+        $crate::const_cstr_ptr_array!(@strs $(
+            $crate::const_cstr_concat!(
+                $crate::__private::env_key($crate::__private::Piece($key).as_str()),
+                "=",
+                $value,
+            )
+        ),*)
+    );
+}
+
+/// Build a [`ConstCStrArray`] of Vulkan extension or layer names, with the `ash` feature,
+/// checking at compile time that each fits in `VK_MAX_EXTENSION_NAME_SIZE` bytes with its
+/// NUL byte, as Vulkan requires.
+///
+/// Each name is anything accepted by `const_cstr!` that produces a `ConstCStr`, or a
+/// `ConstCStr` itself. See `ConstCStrArray::as_vk_names()` for passing the names to `ash`.
+#[cfg(feature = "ash")]
+#[macro_export]
+macro_rules! vk_names {
+    ($($name:expr),* $(,)?) => (
+        $crate::const_cstr_ptr_array!(@strs $(
+            $crate::const_cstr_concat!(
+                $crate::__private::vk_name($crate::__private::Piece($name).as_str())
+            )
+        ),*)
+    );
+}
+
+/// Declare `static` C strings that are computed the first time they are used, as
+/// [`LazyCStr`]s, for strings that need information only available at runtime.
+///
+/// Each value is an expression evaluating to anything accepted by `CString::new()`, such as
+/// a `String` or a `Vec<u8>`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// lazy_cstr! {
+///     pub static USER_AGENT = format!("my-app/{} ({})", "1.0", std::env::consts::OS);
+/// }
+/// # fn main() {
+/// // e.g. `curl_easy_setopt(handle, CURLOPT_USERAGENT, USER_AGENT.as_ptr())`
+/// assert!(USER_AGENT.to_str().unwrap().starts_with("my-app/1.0"));
+/// # }
+/// ```
+///
+/// Panics
+/// ------
+/// On first use, if the value contains a NUL byte.
+#[macro_export]
+macro_rules! lazy_cstr {
+    ($(#[$attr:meta])* $vis:vis static $strname:ident = $value:expr; $($rest:tt)*) => (
+        $(#[$attr])*
+        $vis static $strname: $crate::LazyCStr = $crate::LazyCStr::new(|| {
+            ::std::ffi::CString::new($value)
+                .expect(concat!("`", stringify!($strname), "` contains a NUL byte"))
+        });
+        $crate::lazy_cstr!($($rest)*);
+    );
+    () => ();
+}
+
+/// Get a `CFStringRef` for a string constant, like `CFSTR()` in C, with the `cf` feature on
+/// Apple platforms.
+///
+/// The string is anything accepted by `const_cstr!` that produces a `ConstCStr`. Each
+/// invocation declares a `static` [`ConstCFString`], so the `CFString` is created the first
+/// time that invocation runs and reused, never released, after that:
+///
+/// ```rust,ignore
+/// # #[macro_use] extern crate const_cstr;
+/// // e.g. `CFDictionaryGetValue(info, cfstr!("CFBundleIdentifier").cast())`
+/// let key = cfstr!("CFBundleIdentifier");
+/// ```
+#[cfg(all(feature = "cf", target_vendor = "apple"))]
+#[macro_export]
+macro_rules! cfstr {
+    ($strval:expr $(,)?) => ({
+        static __CONST_CSTR_CF: $crate::ConstCFString =
+            $crate::ConstCFString::new($crate::const_cstr!($strval));
+        __CONST_CSTR_CF.as_cfstring_ref()
+    });
+}
+
+/// Get the `SEL` for an Objective-C selector name, like `@selector()`, with the `objc`
+/// feature on Apple platforms.
+///
+/// The name is anything accepted by `const_cstr!` that produces a `ConstCStr`. Each
+/// invocation declares a `static` [`ConstSel`], so the selector is registered with
+/// `sel_registerName()` the first time that invocation runs and cached after that:
+///
+/// ```rust,ignore
+/// # #[macro_use] extern crate const_cstr;
+/// // e.g. `objc_msgSend(dict, const_sel!("setObject:forKey:"), value, key)`
+/// let sel = const_sel!("setObject:forKey:");
+/// ```
+#[cfg(all(feature = "objc", target_vendor = "apple"))]
+#[macro_export]
+macro_rules! const_sel {
+    ($name:expr $(,)?) => ({
+        static __CONST_CSTR_SEL: $crate::ConstSel =
+            $crate::ConstSel::new($crate::const_cstr!($name));
+        __CONST_CSTR_SEL.as_ptr()
+    });
+}
+
+/// Create a [`LogTag`] for Android logging, with the `android` feature on Android, checking
+/// at compile time that it is no longer than `LogTag::MAX_LEN`.
+///
+/// The tag is anything accepted by `const_cstr!` that produces a `ConstCStr`:
+///
+/// ```rust,ignore
+/// # #[macro_use] extern crate const_cstr;
+/// const TAG: const_cstr::LogTag = log_tag!("MyNativeLib");
+/// ```
+#[cfg(all(feature = "android", target_os = "android"))]
+#[macro_export]
+macro_rules! log_tag {
+    ($tag:expr $(,)?) => ({
+        const __CONST_CSTR_TAG: $crate::LogTag = $crate::LogTag::new($crate::const_cstr!($tag));
+        __CONST_CSTR_TAG
+    });
+}
+
+/// Create a NUL-terminated UTF-16 string constant, as a `ConstWStr`, from a `&'static str`
+/// encoded at compile time.
+///
+/// Accepts the same declaration forms as `const_cstr!`, minus byte strings and companion
+/// constants:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_wstr! {
+///     pub WINDOW_TITLE = "Caf\u{e9}";
+///     static CLASS_NAME = "MyWindowClass";
+/// }
+/// # fn main() {
+/// // e.g. `CreateWindowExW(0, CLASS_NAME.as_ptr(), WINDOW_TITLE.as_ptr(), ...)`
+/// assert_eq!(WINDOW_TITLE.to_wide(), [0x43, 0x61, 0x66, 0xE9]);
+/// # }
+/// ```
+///
+/// As with `const_cstr!`, an interior NUL fails the build.
+#[macro_export]
+macro_rules! const_wstr {
+    ($(#[$attr:meta])* $vis:vis static $strname:ident = $strval:expr; $($rest:tt)*) => (
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = concat!("Wide string constant: `` ", stringify!($strval), " ``")]
+        $vis static $strname: $crate::ConstWStr = $crate::const_wstr!($strval);
+        $crate::const_wstr!($($rest)*);
+    );
+    ($(#[$attr:meta])* $vis:vis $strname:ident = $strval:expr; $($rest:tt)*) => (
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = concat!("Wide string constant: `` ", stringify!($strval), " ``")]
+        $vis const $strname: $crate::ConstWStr = $crate::const_wstr!($strval);
+        $crate::const_wstr!($($rest)*);
+    );
+    ($strval:expr) => ({
+        const __CONST_CSTR_STR: &str = $strval;
+        const __CONST_CSTR_BUF: $crate::__private::WBuf<
+            { $crate::__private::utf16_len(__CONST_CSTR_STR) + 1 }
+        > = $crate::__private::WBuf::encode_with_nul(__CONST_CSTR_STR);
+        $crate::ConstWStr { val: __CONST_CSTR_BUF.as_wide() }
+    });
+    () => ();
+}
+
+/// Create a NUL-terminated UTF-32 string constant, as a `ConstWStr32`, from a
+/// `&'static str` encoded at compile time.
+///
+/// This is `const_wstr!` for platforms where `wchar_t` is 32 bits wide, and accepts the
+/// same declaration forms:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_wstr32! {
+///     pub FORMAT = "%ls\n";
+/// }
+/// # fn main() {
+/// assert_eq!(FORMAT.to_wide(), ['%' as u32, 'l' as u32, 's' as u32, '\n' as u32]);
+/// # }
+/// ```
+///
+/// As with `const_cstr!`, an interior NUL fails the build.
+#[macro_export]
+macro_rules! const_wstr32 {
+    ($(#[$attr:meta])* $vis:vis static $strname:ident = $strval:expr; $($rest:tt)*) => (
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = concat!("Wide string constant: `` ", stringify!($strval), " ``")]
+        $vis static $strname: $crate::ConstWStr32 = $crate::const_wstr32!($strval);
+        $crate::const_wstr32!($($rest)*);
+    );
+    ($(#[$attr:meta])* $vis:vis $strname:ident = $strval:expr; $($rest:tt)*) => (
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = concat!("Wide string constant: `` ", stringify!($strval), " ``")]
+        $vis const $strname: $crate::ConstWStr32 = $crate::const_wstr32!($strval);
+        $crate::const_wstr32!($($rest)*);
+    );
+    ($strval:expr) => ({
+        const __CONST_CSTR_STR: &str = $strval;
+        const __CONST_CSTR_BUF: $crate::__private::W32Buf<
+            { $crate::__private::char_count(__CONST_CSTR_STR) + 1 }
+        > = $crate::__private::W32Buf::encode_with_nul(__CONST_CSTR_STR);
+        $crate::ConstWStr32 { val: __CONST_CSTR_BUF.as_wide() }
+    });
+    () => ();
+}
+
+/// Create a NUL-terminated UCS-2 string constant, as a `ConstCStr16`, from a `&'static str`
+/// encoded at compile time, for UEFI.
+///
+/// Accepts the same declaration forms as `const_wstr!`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// const_cstr16! {
+///     pub VARIABLE_NAME = "BootOrder";
+/// }
+/// # fn main() {
+/// assert_eq!(VARIABLE_NAME.to_string(), "BootOrder");
+/// # }
+/// ```
+///
+/// An interior NUL, or a character outside the Basic Multilingual Plane, which UCS-2 cannot
+/// encode, fails the build:
+///
+/// ```rust,compile_fail
+/// # #[macro_use] extern crate const_cstr;
+/// // error: UCS-2 string contains a character outside the Basic Multilingual Plane at
+/// // index 0
+/// const EMOJI: const_cstr::ConstCStr16 = const_cstr16!("\u{1F600}");
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! const_cstr16 {
+    ($(#[$attr:meta])* $vis:vis static $strname:ident = $strval:expr; $($rest:tt)*) => (
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = concat!("UCS-2 string constant: `` ", stringify!($strval), " ``")]
+        $vis static $strname: $crate::ConstCStr16 = $crate::const_cstr16!($strval);
+        $crate::const_cstr16!($($rest)*);
+    );
+    ($(#[$attr:meta])* $vis:vis $strname:ident = $strval:expr; $($rest:tt)*) => (
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = concat!("UCS-2 string constant: `` ", stringify!($strval), " ``")]
+        $vis const $strname: $crate::ConstCStr16 = $crate::const_cstr16!($strval);
+        $crate::const_cstr16!($($rest)*);
+    );
+    ($strval:expr) => ({
+        const __CONST_CSTR_STR: &str = $strval;
+        const __CONST_CSTR_BUF: $crate::__private::Ucs2Buf<
+            { $crate::__private::char_count(__CONST_CSTR_STR) + 1 }
+        > = $crate::__private::Ucs2Buf::encode_with_nul(__CONST_CSTR_STR);
+        __CONST_CSTR_BUF.as_cstr16()
+    });
+    () => ();
+}
+
+/// Create a `ConstCBytes` holding a `&'static str` encoded, at compile time, as the
+/// "modified UTF-8" expected by JNI for class names, method names and signatures.
+///
+/// Modified UTF-8 differs from UTF-8 in two ways: NUL is encoded as the two bytes `C0 80`,
+/// so it can appear in the string without terminating it, and characters outside the Basic
+/// Multilingual Plane are encoded as a UTF-16 surrogate pair with each half encoded
+/// separately, as in CESU-8. Strings without either are encoded exactly as in UTF-8.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstCBytes;
+///
+/// const CLASS: ConstCBytes = const_mutf8!("com/example/Main");
+/// const EMOJI: ConstCBytes = const_mutf8!("\u{1F600}\0");
+/// # fn main() {
+/// // e.g. `(**env).FindClass.unwrap()(env, CLASS.as_ptr())`
+/// assert_eq!(CLASS.to_bytes(), b"com/example/Main");
+/// assert_eq!(EMOJI.to_bytes(), b"\xED\xA0\xBD\xED\xB8\x80\xC0\x80");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! const_mutf8 {
+    ($strval:expr) => ({
+        const __CONST_CSTR_STR: &str = $strval;
+        const __CONST_CSTR_BUF: $crate::__private::CBuf<
+            { $crate::__private::modified_utf8_len(__CONST_CSTR_STR) + 1 }
+        > = $crate::__private::CBuf::modified_utf8_with_nul(__CONST_CSTR_STR);
+        __CONST_CSTR_BUF.as_cbytes()
+    });
+}
+
+#[cfg(all(feature = "android", target_os = "android"))]
+mod android;
+mod array;
+#[cfg(feature = "ash")]
+mod ash_names;
+mod bytes;
+mod checksum;
+#[cfg(all(feature = "cf", target_vendor = "apple"))]
+mod core_foundation;
+mod cow;
+mod cstr_ptr;
+#[cfg(feature = "defmt")]
+mod defmt_format;
+mod envp;
+mod error;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+mod fuzz;
+pub mod getopt;
+#[cfg(feature = "glib")]
+mod glib_gstr;
+mod intern;
+mod lazy;
+#[cfg(feature = "libloading")]
+mod libloading_symbol;
+mod map;
+pub mod multi_sz;
+#[cfg(all(feature = "objc", target_vendor = "apple"))]
+mod objc_sel;
+mod option;
+mod phf;
+mod ptr_array;
+mod registry;
+#[cfg(feature = "serde")]
+pub mod serde_cstr;
+mod set;
+mod small;
+#[cfg(feature = "subtle")]
+mod subtle_eq;
+#[cfg(any(unix, windows))]
+mod symbol;
+#[cfg(unix)]
+mod unix;
+mod view;
+mod wide;
+#[cfg(all(feature = "windows-sys", windows))]
+mod windows;
+
+#[test]
+fn test_creates_valid_str() {
+    const_cstr! {
+        HELLO_CSTR = "Hello, world!";
+    }
+
+    let cstr = unsafe { CStr::from_ptr(HELLO_CSTR.as_ptr()) };
+
+    assert_eq!(HELLO_CSTR.to_str(), cstr.to_str().unwrap());
+}
+
+#[cfg(test)]
+mod test_creates_pub_str_mod {
+    const_cstr! {
+        pub FIRST = "first";
+        pub SECOND = "second";
+    }
+}
+
+#[test]
+fn test_creates_pub_str() {
+    assert_eq!(test_creates_pub_str_mod::FIRST.to_str(), "first");
+    assert_eq!(test_creates_pub_str_mod::SECOND.to_str(), "second");
+}
+
+#[cfg(test)]
+mod test_creates_mixed_vis_str_mod {
+    const_cstr! {
+        pub PUBLIC = "public";
+        PRIVATE = "private";
+        pub ALSO_PUBLIC = "also public";
+    }
+
+    pub fn private() -> &'static str {
+        PRIVATE.to_str()
+    }
+}
+
+#[test]
+fn test_creates_mixed_vis_str() {
+    assert_eq!(test_creates_mixed_vis_str_mod::PUBLIC.to_str(), "public");
+    assert_eq!(test_creates_mixed_vis_str_mod::private(), "private");
+    assert_eq!(test_creates_mixed_vis_str_mod::ALSO_PUBLIC.to_str(), "also public");
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+mod test_creates_many_strs_mod {
+    const_cstr! {
+        pub S000 = "000"; S001 = "001"; pub static S002 = "002"; pub S003 = "003";
+        S004 = "004"; pub static S005 = "005"; pub S006 = "006"; S007 = "007";
+        pub static S008 = "008"; pub S009 = "009"; S010 = "010"; pub static S011 = "011";
+        pub S012 = "012"; S013 = "013"; pub static S014 = "014"; pub S015 = "015";
+        S016 = "016"; pub static S017 = "017"; pub S018 = "018"; S019 = "019";
+        pub static S020 = "020"; pub S021 = "021"; S022 = "022"; pub static S023 = "023";
+        pub S024 = "024"; S025 = "025"; pub static S026 = "026"; pub S027 = "027";
+        S028 = "028"; pub static S029 = "029"; pub S030 = "030"; S031 = "031";
+        pub static S032 = "032"; pub S033 = "033"; S034 = "034"; pub static S035 = "035";
+        pub S036 = "036"; S037 = "037"; pub static S038 = "038"; pub S039 = "039";
+        S040 = "040"; pub static S041 = "041"; pub S042 = "042"; S043 = "043";
+        pub static S044 = "044"; pub S045 = "045"; S046 = "046"; pub static S047 = "047";
+        pub S048 = "048"; S049 = "049"; pub static S050 = "050"; pub S051 = "051";
+        S052 = "052"; pub static S053 = "053"; pub S054 = "054"; S055 = "055";
+        pub static S056 = "056"; pub S057 = "057"; S058 = "058"; pub static S059 = "059";
+        pub S060 = "060"; S061 = "061"; pub static S062 = "062"; pub S063 = "063";
+        S064 = "064"; pub static S065 = "065"; pub S066 = "066"; S067 = "067";
+        pub static S068 = "068"; pub S069 = "069"; S070 = "070"; pub static S071 = "071";
+        pub S072 = "072"; S073 = "073"; pub static S074 = "074"; pub S075 = "075";
+        S076 = "076"; pub static S077 = "077"; pub S078 = "078"; S079 = "079";
+        pub static S080 = "080"; pub S081 = "081"; S082 = "082"; pub static S083 = "083";
+        pub S084 = "084"; S085 = "085"; pub static S086 = "086"; pub S087 = "087";
+        S088 = "088"; pub static S089 = "089"; pub S090 = "090"; S091 = "091";
+        pub static S092 = "092"; pub S093 = "093"; S094 = "094"; pub static S095 = "095";
+        pub S096 = "096"; S097 = "097"; pub static S098 = "098"; pub S099 = "099";
+        S100 = "100"; pub static S101 = "101"; pub S102 = "102"; S103 = "103";
+        pub static S104 = "104"; pub S105 = "105"; S106 = "106"; pub static S107 = "107";
+        pub S108 = "108"; S109 = "109"; pub static S110 = "110"; pub S111 = "111";
+        S112 = "112"; pub static S113 = "113"; pub S114 = "114"; S115 = "115";
+        pub static S116 = "116"; pub S117 = "117"; S118 = "118"; pub static S119 = "119";
+        pub S120 = "120"; S121 = "121"; pub static S122 = "122"; pub S123 = "123";
+        S124 = "124"; pub static S125 = "125"; pub S126 = "126"; S127 = "127";
+        pub static S128 = "128"; pub S129 = "129"; S130 = "130"; pub static S131 = "131";
+        pub S132 = "132"; S133 = "133"; pub static S134 = "134"; pub S135 = "135";
+        S136 = "136"; pub static S137 = "137"; pub S138 = "138"; S139 = "139";
+        pub static S140 = "140"; pub S141 = "141"; S142 = "142"; pub static S143 = "143";
+        pub S144 = "144"; S145 = "145"; pub static S146 = "146"; pub S147 = "147";
+        S148 = "148"; pub static S149 = "149"; pub S150 = "150"; S151 = "151";
+        pub static S152 = "152"; pub S153 = "153"; S154 = "154"; pub static S155 = "155";
+        pub S156 = "156"; S157 = "157"; pub static S158 = "158"; pub S159 = "159";
+        S160 = "160"; pub static S161 = "161"; pub S162 = "162"; S163 = "163";
+        pub static S164 = "164"; pub S165 = "165"; S166 = "166"; pub static S167 = "167";
+        pub S168 = "168"; S169 = "169"; pub static S170 = "170"; pub S171 = "171";
+        S172 = "172"; pub static S173 = "173"; pub S174 = "174"; S175 = "175";
+        pub static S176 = "176"; pub S177 = "177"; S178 = "178"; pub static S179 = "179";
+        pub S180 = "180"; S181 = "181"; pub static S182 = "182"; pub S183 = "183";
+        S184 = "184"; pub static S185 = "185"; pub S186 = "186"; S187 = "187";
+        pub static S188 = "188"; pub S189 = "189"; S190 = "190"; pub static S191 = "191";
+        pub S192 = "192"; S193 = "193"; pub static S194 = "194"; pub S195 = "195";
+        S196 = "196"; pub static S197 = "197"; pub S198 = "198"; S199 = "199";
+        pub UPPER = upper "upper";
+        pub static S200 = "200"; pub S201 = "201"; S202 = "202"; pub static S203 = "203";
+        pub S204 = "204"; S205 = "205"; pub static S206 = "206"; pub S207 = "207";
+        S208 = "208"; pub static S209 = "209"; pub S210 = "210"; S211 = "211";
+        pub static S212 = "212"; pub S213 = "213"; S214 = "214"; pub static S215 = "215";
+        pub S216 = "216"; S217 = "217"; pub static S218 = "218"; pub S219 = "219";
+        S220 = "220"; pub static S221 = "221"; pub S222 = "222"; S223 = "223";
+        pub static S224 = "224"; pub S225 = "225"; S226 = "226"; pub static S227 = "227";
+        pub S228 = "228"; S229 = "229"; pub static S230 = "230"; pub S231 = "231";
+        S232 = "232"; pub static S233 = "233"; pub S234 = "234"; S235 = "235";
+        pub static S236 = "236"; pub S237 = "237"; S238 = "238"; pub static S239 = "239";
+        pub mod nested {
+            pub NESTED = "nested";
+        }
+    }
+}
+
+#[test]
+fn test_creates_many_strs() {
+    use test_creates_many_strs_mod::*;
+
+    assert_eq!(S000.to_str(), "000");
+    assert_eq!(S198.to_str(), "198");
+    assert_eq!(S237.to_str(), "237");
+    assert_eq!(UPPER.to_str(), "UPPER");
+    assert_eq!(nested::NESTED.to_str(), "nested");
+}
+
+#[cfg(test)]
+mod test_creates_restricted_vis_str_mod {
+    pub mod inner {
+        const_cstr! {
+            pub(crate) CRATE = "crate";
+            pub(super) SUPER = "super";
+            pub(in crate::test_creates_restricted_vis_str_mod) IN_PATH = "in path";
+        }
+    }
+
+    pub fn super_and_in_path() -> (&'static str, &'static str) {
+        (inner::SUPER.to_str(), inner::IN_PATH.to_str())
+    }
+}
+
+#[test]
+fn test_creates_restricted_vis_str() {
+    assert_eq!(test_creates_restricted_vis_str_mod::inner::CRATE.to_str(), "crate");
+    assert_eq!(test_creates_restricted_vis_str_mod::super_and_in_path(), ("super", "in path"));
+}
+
+#[cfg(test)]
+mod test_creates_str_with_attrs_mod {
+    const_cstr! {
+        /// Documented.
+        #[allow(dead_code)]
+        UNUSED = "unused";
+        #[cfg(any())]
+        pub CFGD_OUT = "cfg'd out";
+        #[cfg(all())]
+        pub CFGD_IN = "cfg'd in";
+        #[doc(hidden)]
+        #[deprecated]
+        pub DEPRECATED = "deprecated";
+    }
+
+    pub const CFGD_OUT: &str = "not the macro's";
+}
+
+#[test]
+fn test_creates_str_with_attrs() {
+    assert_eq!(test_creates_str_with_attrs_mod::CFGD_OUT, "not the macro's");
+    assert_eq!(test_creates_str_with_attrs_mod::CFGD_IN.to_str(), "cfg'd in");
+    #[allow(deprecated)]
+    let deprecated = test_creates_str_with_attrs_mod::DEPRECATED;
+    assert_eq!(deprecated.to_str(), "deprecated");
+}
+
+#[cfg(test)]
+mod test_creates_static_str_mod {
+    const_cstr! {
+        pub static STATIC = "static";
+        pub CONST = "const";
+    }
+
+    pub fn static_ptr() -> *const ::std::os::raw::c_char {
+        STATIC.as_ptr()
+    }
+}
+
+#[test]
+fn test_creates_static_str() {
+    let static_ref: &'static ::ConstCStr = &test_creates_static_str_mod::STATIC;
+
+    assert_eq!(static_ref.to_str(), "static");
+    assert_eq!(test_creates_static_str_mod::CONST.to_str(), "const");
+    assert_eq!(static_ref.as_ptr(), test_creates_static_str_mod::static_ptr());
+}
+
+#[test]
+fn test_rvalue_is_const() {
+    const RVALUE: ConstCStr = const_cstr!("rvalue");
+
+    assert_eq!(RVALUE.to_bytes_with_nul(), b"rvalue\0");
+    assert_eq!(const_cstr!("").to_bytes_with_nul(), b"\0");
+}
+
+#[test]
+fn test_creates_str_from_const() {
+    const EXISTING: &str = "existing";
+
+    const_cstr! {
+        FROM_CONST = EXISTING;
+        FROM_CONCAT = concat!("con", "cat");
+    }
+
+    assert_eq!(FROM_CONST.to_str(), EXISTING);
+    assert_eq!(FROM_CONCAT.to_bytes_with_nul(), b"concat\0");
+    assert_eq!(const_cstr!(EXISTING).to_bytes_with_nul(), b"existing\0");
+    assert_eq!(const_cstr!(env!("CARGO_PKG_NAME")).to_str(), "const-cstr");
+}
+
+#[test]
+fn test_concat() {
+    const_cstr! {
+        NAME = "name";
+        EMPTY = "";
+    }
+
+    const SEP: &str = "=";
+
+    assert_eq!(const_cstr_concat!(NAME).to_bytes_with_nul(), b"name\0");
+    assert_eq!(const_cstr_concat!("--", NAME, SEP, "value", EMPTY).to_str(), "--name=value");
+    assert_eq!(const_cstr_concat!(EMPTY, "",).to_bytes_with_nul(), b"\0");
+}
+
+#[test]
+fn test_const_cstr_ident() {
+    const SYMBOL: ConstCStr = const_cstr_ident!(my_symbol);
+
+    assert_eq!(SYMBOL.to_bytes_with_nul(), b"my_symbol\0");
+    assert_eq!(const_cstr_ident!(r#match).to_str(), "match");
+    assert_eq!(const_cstr_ident!(r).to_str(), "r");
+}
+
+#[test]
+fn test_cstr_pkg_metadata() {
+    const USER_AGENT: ConstCStr = cstr_name_version!();
+
+    assert_eq!(cstr_pkg_name!().to_str(), "const-cstr");
+    assert_eq!(cstr_version!().to_str(), env!("CARGO_PKG_VERSION"));
+    assert_eq!(USER_AGENT.to_str(), format!("const-cstr/{}", env!("CARGO_PKG_VERSION")));
+    assert_eq!(
+        cstr_name_version!("-").to_bytes_with_nul(),
+        concat!("const-cstr-", env!("CARGO_PKG_VERSION"), "\0").as_bytes()
+    );
+}
+
+#[test]
+fn test_cstr_location() {
+    const LOCATION: ConstCStr = cstr_location!();
+
+    assert_eq!(LOCATION.to_str(), format!("{}:{}", file!(), line!() - 2));
+    assert_eq!(cstr_file!().to_str(), file!());
+}
+
+#[test]
+fn test_cstr_func() {
+    let func = || cstr_func!();
+
+    assert_eq!(cstr_func!().to_str(), "const_cstr::test_cstr_func");
+    assert_eq!(func().to_str(), "const_cstr::test_cstr_func");
+    assert_eq!(func().as_ptr(), func().as_ptr());
+}
+
+#[test]
+fn test_cstr_format() {
+    use std::sync::Arc;
+
+    let format = |n: u32| cstr_format!("n={}", n);
+    let first = format(1);
+
+    assert_eq!(first.to_bytes_with_nul(), b"n=1\0");
+    assert!(Arc::ptr_eq(&format(1), &first));
+    assert_eq!(format(10).to_str(), Ok("n=10"));
+    assert_eq!(format(1).to_str(), Ok("n=1"));
+    assert!(!Arc::ptr_eq(&format(1), &format(2)));
+    assert_eq!(cstr_format!("{}", "").to_bytes_with_nul(), b"\0");
+}
+
+#[test]
+fn test_cstr_format_reentrant() {
+    use std::ffi::CStr;
+    use std::fmt;
+    use std::sync::Arc;
+
+    struct Depth(u32);
+
+    impl fmt::Display for Depth {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.0 {
+                0 => f.write_str("0"),
+                n => write!(f, "{}<{}", n, nested(n - 1).to_str().unwrap()),
+            }
+        }
+    }
+
+    fn nested(n: u32) -> Arc<CStr> {
+        cstr_format!("{}", Depth(n))
+    }
+
+    assert_eq!(nested(2).to_str(), Ok("2<1<0"));
+}
+
+#[test]
+#[should_panic(expected = "interior NUL byte at index 1")]
+fn test_cstr_format_nul() {
+    cstr_format!("a{}", '\0');
+}
+
+#[test]
+fn test_const_cstr_padded() {
+    const NAME: &str = "lo";
+    const PADDED: [c_char; 4] = const_cstr_padded!(NAME);
+
+    assert_eq!(PADDED, [b'l' as c_char, b'o' as c_char, 0, 0]);
+    assert_eq!(const_cstr_padded!(3, b"\x7F\x01"), [0x7F, 0x01, 0]);
+    assert_eq!(const_cstr_padded!(1, ""), [0]);
+}
+
+#[test]
+fn test_export_cstr() {
+    export_cstr! {
+        static const_cstr_test_export_bytes: [c_char] = b"bytes";
+        static const_cstr_test_export_ptr: ConstCStrPtr = concat!("poin", "ter");
+    }
+
+    // Refers to the symbols as C code would, by name alone.
+    mod c {
+        use std::os::raw::c_char;
+
+        extern "C" {
+            pub static const_cstr_test_export_bytes: [c_char; 6];
+            pub static const_cstr_test_export_ptr: *const c_char;
+        }
+    }
+
+    unsafe {
+        assert_eq!(CStr::from_ptr(c::const_cstr_test_export_bytes.as_ptr()).to_bytes(), b"bytes");
+        assert_eq!(CStr::from_ptr(c::const_cstr_test_export_ptr).to_str(), Ok("pointer"));
+    }
+
+    assert_eq!(const_cstr_test_export_ptr.get().to_str(), "pointer");
+}
+
+#[cfg(test)]
+mod test_const_cstr_pool_mod {
+    pub const SHARED: &str = "shared";
+
+    const_cstr_pool! {
+        pub mod pool {
+            pub SHORT = "ed";
+            /// Documented.
+            pub SHARED = super::SHARED;
+            pub(crate) AGAIN = "shared";
+            pub EMPTY = "";
+            pub OTHER = concat!("oth", "er");
+        }
+    }
+}
+
+#[test]
+fn test_const_cstr_pool() {
+    use test_const_cstr_pool_mod::pool::*;
+
+    let strings: Vec<_> = [SHORT, SHARED, AGAIN, EMPTY, OTHER].iter()
+        .map(|cstr| cstr.to_bytes_with_nul())
+        .collect();
+
+    assert_eq!(strings, [&b"ed\0"[..], b"shared\0", b"shared\0", b"\0", b"other\0"]);
+    assert_eq!(SHARED.as_ptr(), AGAIN.as_ptr());
+    assert_eq!(SHORT.as_ptr(), SHARED.as_ptr().wrapping_add(4));
+    assert_eq!(EMPTY.as_ptr(), SHARED.as_ptr().wrapping_add(6));
+}
+
+#[test]
+fn test_deref_to_cstr() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    let cstr: &CStr = &HELLO;
+
+    assert_eq!(cstr.to_bytes(), b"Hello");
+    assert_eq!(HELLO.to_string_lossy(), "Hello");
+    assert_eq!(HELLO.to_str(), "Hello");
+    assert_eq!(*HELLO, *HELLO.as_cstr());
+}
+
+#[test]
+fn test_as_ref() {
+    fn c_len<S: AsRef<CStr>>(s: S) -> usize {
+        s.as_ref().to_bytes().len()
+    }
+
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    assert_eq!(c_len(HELLO), 5);
+    assert_eq!(AsRef::<str>::as_ref(&HELLO), "Hello");
+    assert_eq!(AsRef::<[u8]>::as_ref(&HELLO), b"Hello");
+}
+
+#[test]
+fn test_borrow_as_cstr() {
+    use std::collections::{BTreeSet, HashSet};
+    use std::collections::hash_map::DefaultHasher;
+
+    fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    const_cstr! {
+        A = "a";
+        AB = "ab";
+    }
+
+    let hashed: HashSet<ConstCStr> = [A, AB].iter().cloned().collect();
+    let ordered: BTreeSet<ConstCStr> = [AB, A].iter().cloned().collect();
+
+    assert_eq!(hash(&A), hash(A.as_cstr()));
+    assert!(hashed.contains(AB.as_cstr()));
+    assert!(!hashed.contains(const_cstr!("b").as_cstr()));
+    assert!(ordered.contains(A.as_cstr()));
+    assert_eq!(ordered.iter().next(), Some(&A));
+    assert_eq!(A.cmp(&AB), A.as_cstr().cmp(AB.as_cstr()));
+}
+
+#[test]
+fn test_display() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    assert_eq!(HELLO.to_string(), "Hello");
+    assert_eq!(format!("<{:>7}>", HELLO), "<  Hello>");
+    assert_eq!(format!("{:.2}", HELLO), "He");
+}
+
+#[test]
+fn test_debug() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    assert_eq!(format!("{:?}", HELLO), format!("{:?}", HELLO.as_cstr()));
+    assert_eq!(format!("{:?}", const_cstr!("tab\t\"\u{e9}\"")), "\"tab\\t\\\"\u{e9}\\\"\"");
+    assert_eq!(format!("{:?}", [HELLO]), r#"["Hello"]"#);
+}
+
+#[test]
+fn test_into_cstr_and_ptr() {
+    fn c_strlen<P: Into<*const c_char>>(ptr: P) -> usize {
+        unsafe { CStr::from_ptr(ptr.into()) }.to_bytes().len()
+    }
+
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    let cstr: &'static CStr = HELLO.into();
+    let ptr: *const c_char = HELLO.into();
+
+    assert_eq!(cstr, HELLO.as_cstr());
+    assert_eq!(ptr, HELLO.as_ptr());
+    assert_eq!(c_strlen(HELLO), 5);
+    assert!(matches!(Cow::from(HELLO), Cow::Borrowed(cstr) if cstr == HELLO.as_cstr()));
+}
+
+#[test]
+fn test_from_cstr() {
+    const FROM_C: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"Hello\0") };
+
+    const_cstr! {
+        HELLO, HELLO_STR = FROM_C;
+    }
+
+    assert_eq!(ConstCStr::from_cstr(FROM_C).to_bytes_with_nul(), b"Hello\0");
+    assert_eq!(HELLO.to_str(), "Hello");
+    assert_eq!(HELLO_STR, "Hello");
+    assert_eq!(const_cstr!(FROM_C), HELLO);
+    assert_eq!(const_cstr_padded!(8, FROM_C), const_cstr_padded!(8, "Hello"));
+}
+
+#[test]
+#[should_panic(expected = "C string is not valid UTF-8 at index 1")]
+fn test_from_cstr_invalid() {
+    ConstCStr::from_cstr(unsafe { CStr::from_bytes_with_nul_unchecked(b"a\xFF\0") });
+}
+
+#[test]
+fn test_from_str_with_nul() {
+    assert_eq!(ConstCStr::try_from("Hello\0"), Ok(const_cstr!("Hello")));
+    assert_eq!(ConstCStr::try_from("\0"), Ok(const_cstr!("")));
+    assert_eq!(ConstCStr::try_from("Hello"), Err(FromStrWithNulError::NotNulTerminated));
+    assert_eq!(ConstCStr::try_from(""), Err(FromStrWithNulError::NotNulTerminated));
+    assert_eq!(
+        ConstCStr::try_from("\0\0"),
+        Err(FromStrWithNulError::InteriorNul { position: 0 })
+    );
+    assert_eq!(
+        FromStrWithNulError::InteriorNul { position: 2 }.to_string(),
+        "C string contains an interior NUL byte at index 2"
+    );
+}
+
+#[test]
+fn test_default() {
+    #[derive(Default)]
+    struct Config {
+        name: ConstCStr,
+    }
+
+    let config = Config::default();
+
+    assert_eq!(config.name.to_bytes_with_nul(), b"\0");
+    assert_eq!(unsafe { CStr::from_ptr(config.name.as_ptr()) }.to_bytes(), b"");
+}
+
+#[test]
+fn test_bytes_and_chars() {
+    const_cstr! {
+        CAFE = "caf\u{e9}";
+    }
+
+    assert_eq!(CAFE.bytes().len(), 5);
+    assert_eq!(CAFE.bytes().next_back(), Some(0xA9));
+    assert!(CAFE.bytes().all(|byte| byte != 0));
+    assert_eq!(CAFE.chars().rev().collect::<String>(), "\u{e9}fac");
+    assert_eq!(const_cstr!("").bytes().next(), None);
+}
+
+#[test]
+fn test_suffix_from() {
+    const_cstr! {
+        CAFE = "caf\u{e9}";
+    }
+
+    assert_eq!(CAFE.suffix_from(0), CAFE);
+    assert_eq!(CAFE.suffix_from(3).to_bytes_with_nul(), "\u{e9}\0".as_bytes());
+    assert_eq!(CAFE.suffix_from(5).to_bytes_with_nul(), b"\0");
+}
+
+#[test]
+#[should_panic(expected = "suffix index 6 is out of range for C string of length 5")]
+fn test_suffix_from_out_of_range() {
+    const_cstr!("caf\u{e9}").suffix_from(6);
+}
+
+#[test]
+#[should_panic(expected = "suffix index 4 is not a char boundary")]
+fn test_suffix_from_not_char_boundary() {
+    const_cstr!("caf\u{e9}").suffix_from(4);
+}
+
+#[test]
+fn test_cmp_c() {
+    const_cstr! {
+        A = "a";
+        AB = "ab";
+        HIGH = "\u{e9}";
+    }
+
+    assert_eq!(A.cmp_c(AB.as_cstr()), Ordering::Less);
+    assert_eq!(AB.cmp_c(A.as_cstr()), Ordering::Greater);
+    assert_eq!(A.cmp_c(A.as_cstr()), Ordering::Equal);
+    // `strcmp()` compares bytes as `unsigned char`.
+    assert_eq!(HIGH.cmp_c(A.as_cstr()), Ordering::Greater);
+    assert_eq!(const_cstr!("").cmp_c(A.as_cstr()), Ordering::Less);
+    assert!(A < AB && AB < HIGH);
+}
+
+#[test]
+fn test_starts_ends_with_contains() {
+    const_cstr! {
+        EXTENSION = "GL_ARB_sync";
+    }
+
+    assert!(EXTENSION.starts_with("GL_") && !EXTENSION.starts_with("WGL_"));
+    assert!(EXTENSION.ends_with("sync") && !EXTENSION.ends_with("sync\0"));
+    assert!(EXTENSION.contains("_ARB_") && !EXTENSION.contains("\0"));
+    assert!(const_cstr!("").starts_with("") && const_cstr!("").contains(""));
+}
+
+#[test]
+fn test_strip_prefix_suffix() {
+    const_cstr! {
+        EXTENSION = "GL_EXT_blend_minmax";
+    }
+
+    assert_eq!(EXTENSION.strip_prefix("GL_EXT_"), Some("blend_minmax"));
+    assert_eq!(EXTENSION.strip_prefix("GL_ARB_"), None);
+    assert_eq!(EXTENSION.strip_suffix("_minmax"), Some("GL_EXT_blend"));
+    assert_eq!(EXTENSION.strip_suffix("\0"), None);
+
+    let short = EXTENSION.strip_prefix_cstr("GL_EXT_").unwrap();
+    assert_eq!(short.to_bytes_with_nul(), b"blend_minmax\0");
+    assert_eq!(EXTENSION.strip_prefix_cstr("GL_ARB_"), None);
+}
+
+#[test]
+fn test_eq_ptr() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    unsafe {
+        assert!(HELLO.eq_ptr(const_cstr!("Hello").as_ptr()));
+        assert!(!HELLO.eq_ptr(const_cstr!("Hell").as_ptr()));
+        assert!(!HELLO.eq_ptr(const_cstr!("Hello!").as_ptr()));
+        assert!(!HELLO.eq_ptr(const_cstr!("").as_ptr()));
+        assert!(const_cstr!("").eq_ptr(const_cstr!("").as_ptr()));
+        // Stops at the first difference, without reading past it.
+        assert!(!HELLO.eq_ptr(b"J".as_ptr() as *const c_char));
+    }
+}
+
+#[test]
+fn test_eq_ignore_ascii_case() {
+    const_cstr! {
+        PATH = "Path";
+    }
+
+    assert!(PATH.eq_ignore_ascii_case("PATH") && PATH.eq_ignore_ascii_case("path"));
+    assert!(!PATH.eq_ignore_ascii_case("PATHS") && !PATH.eq_ignore_ascii_case("pat"));
+    assert!(!const_cstr!("\u{e9}").eq_ignore_ascii_case("\u{c9}"));
+    assert!(PATH.eq_ignore_ascii_case_cstr(const_cstr!("pAtH").as_cstr()));
+    assert!(!PATH.eq_ignore_ascii_case_cstr(const_cstr!("PATH_").as_cstr()));
+
+    unsafe {
+        assert!(PATH.eq_ignore_ascii_case_ptr(const_cstr!("PATH").as_ptr()));
+        assert!(!PATH.eq_ignore_ascii_case_ptr(const_cstr!("PAT").as_ptr()));
+        assert!(!PATH.eq_ignore_ascii_case_ptr(const_cstr!("PATHEXT").as_ptr()));
+    }
+}
+
+#[test]
+fn test_as_ptr_and_len() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    const PTR_AND_LEN: (*const c_char, usize) = HELLO.as_ptr_and_len();
+
+    assert_eq!(PTR_AND_LEN, (HELLO.as_ptr(), 5));
+    assert_eq!(const_cstr!("").as_ptr_and_len().1, 0);
+}
+
+#[test]
+fn test_as_u8_i8_ptr() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    assert_eq!(HELLO.as_u8_ptr(), HELLO.to_bytes_with_nul().as_ptr());
+    assert_eq!(HELLO.as_i8_ptr().cast::<c_char>(), HELLO.as_ptr());
+}
+
+#[test]
+fn test_as_c_char_slice() {
+    const_cstr! {
+        HI = "Hi";
+    }
+
+    const SLICE: &[c_char] = HI.as_c_char_slice();
+
+    assert_eq!(SLICE, &[b'H' as c_char, b'i' as c_char, 0]);
+    assert_eq!(SLICE.as_ptr(), HI.as_ptr());
+}
+
+#[test]
+fn test_hash_fnv1a_crc32() {
+    const_cstr! {
+        DIGITS = "123456789";
+    }
+
+    const HASH: u64 = DIGITS.hash_fnv1a();
+    const CRC: u32 = DIGITS.crc32();
+
+    assert_eq!(HASH, 0x06d5_5739_23c6_cdfc);
+    assert_eq!(CRC, 0xcbf4_3926);
+    assert_eq!(const_cstr!("").hash_fnv1a(), 0xcbf2_9ce4_8422_2325);
+}
+
+#[test]
+fn test_copy_to() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    let mut buf = [1 as c_char; 8];
+
+    assert_eq!(HELLO.copy_to(&mut buf), Ok(()));
+    assert_eq!(unsafe { CStr::from_ptr(buf.as_ptr()) }, HELLO.as_cstr());
+    assert_eq!(buf[6..], [1, 1]);
+    assert_eq!(HELLO.copy_to(&mut buf[..6]), Ok(()));
+
+    let mut small = [1 as c_char; 5];
+
+    assert_eq!(
+        HELLO.copy_to(&mut small),
+        Err(TooLongError { len_with_nul: 6, capacity: 5 })
+    );
+    assert_eq!(small, [1; 5]);
+}
+
+#[test]
+fn test_copy_to_truncated() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    let mut buf = [1 as c_char; 4];
+
+    assert_eq!(HELLO.copy_to_truncated(&mut buf), 3);
+    assert_eq!(unsafe { CStr::from_ptr(buf.as_ptr()) }.to_bytes(), b"Hel");
+
+    let mut buf = [1 as c_char; 8];
+
+    assert_eq!(HELLO.copy_to_truncated(&mut buf), 5);
+    assert_eq!(unsafe { CStr::from_ptr(buf.as_ptr()) }, HELLO.as_cstr());
+    assert_eq!(HELLO.copy_to_truncated(&mut []), 0);
+}
+
+#[test]
+fn test_eq_ptr_n() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    let full = b"Hello" as *const u8 as *const c_char;
+
+    unsafe {
+        assert!(HELLO.eq_ptr_n(full, 5));
+        assert!(HELLO.eq_ptr_n(full, 3));
+        assert!(HELLO.eq_ptr_n(const_cstr!("Hello").as_ptr(), 100));
+        assert!(!HELLO.eq_ptr_n(const_cstr!("Hell").as_ptr(), 100));
+        assert!(!HELLO.eq_ptr_n(const_cstr!("Hello!").as_ptr(), 100));
+        assert!(const_cstr!("").eq_ptr_n(full, 0));
+        assert!(!const_cstr!("").eq_ptr_n(full, 1));
+    }
+}
+
+#[test]
+fn test_len() {
+    const_cstr! {
+        HELLO = "Hello";
+        EMPTY = "";
+    }
+
+    const LEN: usize = HELLO.len();
+
+    assert_eq!(LEN, 5);
+    assert_eq!(HELLO.len_with_nul(), 6);
+    assert!(!HELLO.is_empty());
+    assert_eq!(EMPTY.len(), 0);
+    assert_eq!(EMPTY.len_with_nul(), 1);
+    assert!(EMPTY.is_empty());
+}
+
+#[test]
+fn test_const_accessors() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    const STR: &str = HELLO.to_str();
+    const BYTES: &[u8] = HELLO.to_bytes();
+    const BYTES_WITH_NUL: &[u8] = HELLO.to_bytes_with_nul();
+    const PTR: *const c_char = HELLO.as_ptr();
+    const CSTR: &CStr = HELLO.as_cstr();
+
+    assert_eq!(STR, "Hello");
+    assert_eq!(BYTES, b"Hello");
+    assert_eq!(BYTES_WITH_NUL, b"Hello\0");
+    assert_eq!(PTR, HELLO.as_ptr());
+    assert_eq!(CSTR, HELLO.as_cstr());
+    assert_eq!(HELLO.as_non_null().as_ptr() as *const c_char, PTR);
+}
+
+#[test]
+fn test_from_str_with_nul_unchecked() {
+    const HELLO: ConstCStr = unsafe { ConstCStr::from_str_with_nul_unchecked("Hello\0") };
+
+    assert_eq!(HELLO.to_str(), "Hello");
+    assert_eq!(HELLO.as_cstr(), const_cstr!("Hello").as_cstr());
+}
+
+#[test]
+fn test_as_non_null() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    let ptr = HELLO.as_non_null();
+
+    assert_eq!(ptr.as_ptr() as *const c_char, HELLO.as_ptr());
+    assert_eq!(unsafe { CStr::from_ptr(ptr.as_ptr()) }, HELLO.as_cstr());
+}
+
+#[test]
+fn test_to_cstring() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    let owned = HELLO.to_cstring();
+
+    assert_eq!(owned.as_bytes_with_nul(), b"Hello\0");
+    assert_ne!(owned.as_ptr(), HELLO.as_ptr());
+    assert_eq!(CString::from(HELLO), owned);
+    assert_eq!(const_cstr!("").to_cstring().as_bytes_with_nul(), b"\0");
+}
+
+#[test]
+fn test_cmp_with_strings() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    let returned = CString::new("Hello").unwrap();
+    let owned = String::from("Hello");
+
+    assert!(HELLO == "Hello");
+    assert!("Hello" == HELLO);
+    assert!(HELLO == *"Hello");
+    assert!(HELLO == owned);
+    assert!(HELLO == *returned.as_c_str());
+    assert!(returned == HELLO);
+    assert!(HELLO != "Hello\0");
+    assert!(HELLO < "Help");
+    assert!(CString::new("Hell").unwrap() < HELLO);
+    assert!(HELLO <= *returned.as_c_str());
+}
+
+#[test]
+fn test_const_cstr_path() {
+    const DIR: ConstCStr = const_cstr!("share");
+    const PATH: ConstCStr = const_cstr_path!("usr", DIR, "app",);
+
+    let expected = ["usr", "share", "app"].join(if cfg!(windows) { "\\" } else { "/" });
+
+    assert_eq!(PATH.to_str(), expected);
+    assert_eq!(const_cstr_path!("single").to_str(), "single");
+    assert_eq!(const_wstr_path!("usr", DIR, "app").to_string(), expected);
+}
+
+#[test]
+fn test_include_cstr() {
+    const LICENSE: ConstCStr = include_cstr!("../LICENSE-MIT");
+
+    assert_eq!(LICENSE.to_str(), include_str!("../LICENSE-MIT"));
+}
+
+#[cfg(test)]
+mod test_creates_companion_str_mod {
+    const_cstr! {
+        pub NAME, NAME_STR = "name";
+        #[allow(dead_code)]
+        pub static STATIC, STATIC_STR = "static";
+    }
+}
+
+#[test]
+fn test_creates_companion_str() {
+    use test_creates_companion_str_mod::*;
+
+    assert_eq!(NAME.to_str(), "name");
+    assert_eq!(NAME_STR, "name");
+    assert_eq!(STATIC.to_str(), "static");
+    assert_eq!(STATIC_STR, "static");
+}
+
+#[cfg(test)]
+mod test_creates_case_transformed_str_mod {
+    const_cstr! {
+        pub UPPER = upper "content-type";
+        pub static LOWER, LOWER_STR = lower "X-Forwarded-For";
+        pub UNCHANGED = upper "\u{e9}t\u{e9}";
+    }
+}
+
+#[test]
+fn test_creates_case_transformed_str() {
+    use test_creates_case_transformed_str_mod::*;
+
+    assert_eq!(UPPER.to_bytes_with_nul(), b"CONTENT-TYPE\0");
+    assert_eq!(LOWER.to_str(), "x-forwarded-for");
+    assert_eq!(LOWER_STR, "x-forwarded-for");
+    assert_eq!(UNCHANGED.to_str(), "\u{e9}T\u{e9}");
+    assert_eq!(const_cstr!(lower "ABC").to_str(), "abc");
+}
+
+#[cfg(test)]
+mod test_creates_dedented_str_mod {
+    const_cstr! {
+        pub SHADER, SHADER_STR = dedent "
+            void main() {
+            \tgl_FragColor = vec4(1.0);
+              \t
+            }";
+        pub static BLANK = dedent "
+            \n  \n";
+    }
+}
+
+#[test]
+fn test_creates_dedented_str() {
+    use test_creates_dedented_str_mod::*;
+
+    assert_eq!(SHADER.to_str(), "void main() {\n\tgl_FragColor = vec4(1.0);\n\n}");
+    assert_eq!(SHADER_STR, SHADER.to_str());
+    assert_eq!(BLANK.to_bytes_with_nul(), b"\n\n\0");
+    assert_eq!(const_cstr!(dedent "  one\n   two").to_str(), "one\n two");
+    assert_eq!(const_cstr!(dedent "flush\n    indented").to_str(), "flush\n    indented");
+    assert_eq!(const_cstr!(dedent "").to_str(), "");
+}
+
+#[cfg(test)]
+mod test_creates_max_len_str_mod {
+    const_cstr! {
+        pub SHORT: max_len(5) = "short";
+        pub static EMPTY, EMPTY_STR: max_len(0) = "";
+        pub UPPER: max_len(3) = upper "abc";
+    }
+}
+
+#[test]
+fn test_creates_max_len_str() {
+    use test_creates_max_len_str_mod::*;
+
+    assert_eq!(SHORT.to_str(), "short");
+    assert_eq!(EMPTY.to_str(), EMPTY_STR);
+    assert_eq!(UPPER.to_str(), "ABC");
+}
+
+#[cfg(test)]
+mod test_creates_ascii_str_mod {
+    const_cstr! {
+        pub ASCII: ascii, max_len(5) = "ascii";
+
+        pub mod all {
+            #![ascii]
+            pub FIRST = "first";
+            pub SECOND: max_len(6) = lower "SECOND";
+        }
+
+        pub OUTSIDE = "caf\u{e9}";
+    }
+}
+
+#[test]
+fn test_creates_ascii_str() {
+    use test_creates_ascii_str_mod::*;
+
+    assert_eq!(ASCII.to_str(), "ascii");
+    assert_eq!(all::FIRST.to_str(), "first");
+    assert_eq!(all::SECOND.to_str(), "second");
+    assert_eq!(OUTSIDE.to_str(), "caf\u{e9}");
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test_creates_str_in_section_mod {
+    const_cstr! {
+        pub static SECTION: section(".const_cstr_test") = "in section";
+        pub static USED, USED_STR: used, max_len(4) = upper "used";
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_creates_str_in_section() {
+    use test_creates_str_in_section_mod::*;
+
+    assert_eq!(SECTION.to_bytes_with_nul(), b"in section\0");
+    assert_eq!(USED.to_str(), "USED");
+    assert_eq!(USED_STR, "USED");
+}
+
+#[cfg(test)]
+mod test_creates_str_mod_mod {
+    const_cstr! {
+        pub mod outer {
+            pub OUTER = "outer";
+
+            pub(crate) mod inner {
+                pub INNER = "inner";
+            }
+        }
+        pub AFTER = "after";
+    }
+}
+
+#[test]
+fn test_creates_str_mod() {
+    use test_creates_str_mod_mod::*;
+
+    assert_eq!(outer::OUTER.to_str(), "outer");
+    assert_eq!(outer::inner::INNER.to_str(), "inner");
+    assert_eq!(AFTER.to_str(), "after");
+}
+
+#[cfg(test)]
+mod test_c_str_enum_mod {
+    c_str_enum! {
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        pub enum Single {
+            Only = "only"
+        }
+
+        /// Documented.
+        #[derive(Copy, Clone)]
+        pub(crate) enum Multi {
+            /// Variant docs.
+            First = "first",
+            Second = concat!("sec", "ond"),
+        }
+    }
+}
+
+#[test]
+fn test_c_str_enum() {
+    use test_c_str_enum_mod::*;
+
+    assert_eq!(Single::Only.as_cstr().to_str(), "only");
+    assert_eq!(Multi::First.as_cstr().to_str(), "first");
+    assert_eq!(Multi::Second.as_cstr().to_bytes_with_nul(), b"second\0");
+
+    let cstr = unsafe { CStr::from_ptr(Multi::Second.as_ptr()) };
+    assert_eq!(cstr.to_str(), Ok("second"));
+}
+
+#[test]
+fn test_const_argv() {
+    const ARGV: &[*const c_char] = const_argv!("prog", b"\xFF", concat!("--", "flag"),);
+    const EMPTY: &[*const c_char] = const_argv!();
+
+    let args: Vec<_> = ARGV[..ARGV.len() - 1].iter()
+        .map(|&arg| unsafe { CStr::from_ptr(arg) }.to_bytes())
+        .collect();
+
+    assert_eq!(args, [&b"prog"[..], b"\xFF", b"--flag"]);
+    assert!(ARGV[ARGV.len() - 1].is_null());
+    assert_eq!(EMPTY.len(), 1);
+    assert!(EMPTY[0].is_null());
+}
+
+#[cfg(test)]
+mod test_const_cstr_table_mod {
+    use std::os::raw::{c_char, c_int};
+
+    #[repr(C)]
+    pub struct Reg {
+        pub name: *const c_char,
+        pub func: Option<extern "C" fn() -> c_int>,
+    }
+
+    #[repr(C)]
+    pub struct Def {
+        pub name: *const c_char,
+        pub func: Option<extern "C" fn() -> c_int>,
+        pub flags: c_int,
+        pub doc: *const c_char,
+    }
+
+    pub extern "C" fn one() -> c_int { 1 }
+    pub extern "C" fn two() -> c_int { 2 }
+}
+
+#[test]
+fn test_const_cstr_table() {
+    use test_const_cstr_table_mod::*;
+
+    const REGS: &[Reg] = const_cstr_table! {
+        Reg { name, func };
+        "one" => Some(one),
+        "two" => Some(two),
+    };
+    const DEFS: &[Def] = const_cstr_table! {
+        test_const_cstr_table_mod::Def { name };
+        "one" => { func: Some(one), flags: 3 },
+        "none" => {},
+    };
+
+    assert_eq!(REGS.len(), 3);
+    assert_eq!(unsafe { CStr::from_ptr(REGS[1].name) }.to_str(), Ok("two"));
+    assert_eq!(REGS[1].func.map(|func| func()), Some(2));
+    assert!(REGS[2].name.is_null() && REGS[2].func.is_none());
+
+    assert_eq!(DEFS.len(), 3);
+    assert_eq!(DEFS[0].flags, 3);
+    assert!(DEFS[0].doc.is_null());
+    assert_eq!(unsafe { CStr::from_ptr(DEFS[1].name) }.to_str(), Ok("none"));
+    assert!(DEFS[1].func.is_none());
+    assert!(DEFS[2].name.is_null());
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod test_derive_as_cstr_mod {
+    use {AsCStr, FromCStr};
+
+    #[derive(AsCStr)]
+    pub enum Named {
+        Plain,
+        #[cstr = "renamed"]
+        Renamed,
+        r#Raw,
+    }
+
+    #[derive(AsCStr, FromCStr, Copy, Clone, Debug, PartialEq)]
+    pub enum RoundTrip {
+        First,
+        #[cstr = "second"]
+        Second,
+    }
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_derive_as_cstr() {
+    use test_derive_as_cstr_mod::Named;
+    use AsCStr;
+
+    assert_eq!(Named::Plain.as_cstr().to_str(), "Plain");
+    assert_eq!(Named::Renamed.as_cstr().to_str(), "renamed");
+    assert_eq!(Named::Raw.as_cstr().to_bytes_with_nul(), b"Raw\0");
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn test_derive_from_cstr() {
+    use test_derive_as_cstr_mod::RoundTrip;
+    use {AsCStr, FromCStr};
+
+    for &variant in &[RoundTrip::First, RoundTrip::Second] {
+        let cstr = variant.as_cstr();
+        assert_eq!(RoundTrip::from_cstr(cstr.as_cstr()), Some(variant));
+        assert_eq!(unsafe { RoundTrip::from_ptr(cstr.as_ptr()) }, Some(variant));
+    }
+
+    assert_eq!(RoundTrip::from_cstr(const_cstr!("Second").as_cstr()), None);
+    assert_eq!(RoundTrip::from_cstr(const_cstr!("").as_cstr()), None);
+    assert_eq!(unsafe { RoundTrip::from_ptr(::std::ptr::null()) }, None);
+}
+
+#[cfg(feature = "proc-macro")]
+#[test]
+fn test_cstr_proc_macro() {
+    const NAME: &CStr = cstr!("name");
+
+    assert_eq!(NAME.to_bytes_with_nul(), b"name\0");
+    assert_eq!(cstr!(b"\x80").to_bytes(), b"\x80");
+    assert_eq!(cstr!("").to_bytes_with_nul(), b"\0");
+}
+
+#[cfg(all(test, feature = "linkme"))]
+mod test_register_cstr_mod {
+    const_cstr_slice! {
+        /// Documented.
+        pub static NAMES;
+    }
+
+    pub mod plugin {
+        register_cstr! {
+            in super::NAMES;
+            pub FIRST = "first";
+            SECOND = concat!("sec", "ond");
+        }
+    }
+}
+
+#[cfg(feature = "linkme")]
+#[test]
+fn test_register_cstr() {
+    use test_register_cstr_mod::{plugin, NAMES};
+
+    let mut names: Vec<_> = NAMES.iter().map(|name| name.to_str()).collect();
+    names.sort();
+
+    assert_eq!(names, ["first", "second"]);
+    assert!(NAMES.iter().any(|name| name.as_ptr() == plugin::FIRST.as_ptr()));
 }
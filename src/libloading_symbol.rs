@@ -0,0 +1,60 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Symbol lookup in a `libloading::Library`, enabled by the `libloading` feature.
+//!
+//! `Library::get()` copies a symbol name into a new `CString` unless it already ends with a
+//! NUL byte, so passing the constant with its NUL byte saves an allocation per lookup.
+use libloading::{Error, Library, Symbol};
+
+use ConstCStr;
+
+impl ConstCStr {
+    /// Looks up the symbol named by the wrapped string in `library`, passing the string
+    /// with its NUL terminating byte so that it is not copied.
+    ///
+    /// ```rust,ignore
+    /// # #[macro_use] extern crate const_cstr;
+    /// const_cstr! {
+    ///     GET_INSTANCE_PROC_ADDR = "vkGetInstanceProcAddr";
+    /// }
+    ///
+    /// let vulkan = unsafe { libloading::Library::new("libvulkan.so.1")? };
+    /// let get_instance_proc_addr = unsafe {
+    ///     GET_INSTANCE_PROC_ADDR.get_symbol::<PFN_vkGetInstanceProcAddr>(&vulkan)?
+    /// };
+    /// ```
+    ///
+    /// Safety
+    /// ------
+    /// `T` must be the type of the symbol, as for `Library::get()`.
+    pub unsafe fn get_symbol<'lib, T>(
+        &self,
+        library: &'lib Library,
+    ) -> Result<Symbol<'lib, T>, Error> {
+        library.get(self.to_bytes_with_nul())
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_get_symbol() {
+    use std::os::raw::c_char;
+
+    let this = Library::from(::libloading::os::unix::Library::this());
+
+    let strlen = unsafe {
+        const_cstr!("strlen")
+            .get_symbol::<unsafe extern "C" fn(*const c_char) -> usize>(&this)
+            .unwrap()
+    };
+
+    assert_eq!(unsafe { strlen(const_cstr!("four").as_ptr()) }, 4);
+    assert!(unsafe { const_cstr!("const_cstr_no_such_symbol").get_symbol::<*const u8>(&this) }
+        .is_err());
+}
@@ -0,0 +1,191 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+use std::ffi::CStr;
+use std::fmt;
+use std::slice;
+
+use phf;
+use ConstCStr;
+
+/// A map from C strings to values of type `V`, built at compile time by the
+/// `const_cstr_map!` macro.
+///
+/// Lookups use a perfect hash function computed during `const` evaluation, so each one
+/// costs a single hash of the key and at most one string comparison, with no allocation.
+pub struct ConstCStrMap<V: 'static> {
+    /// The entries of the map, in the order they were given to the macro.
+    entries: &'static [(ConstCStr, V)],
+    seed: u64,
+    disps: &'static [(u32, u32)],
+    slots: &'static [u32],
+}
+
+impl<V: 'static> ConstCStrMap<V> {
+    /// Not intended to be called directly. Public only to allow initialization by the
+    /// `const_cstr_map!` macro.
+    ///
+    /// Safety
+    /// ------
+    /// `disps` and `slots` must have one element for each of `entries`, and each of `slots`
+    /// must be the index of one of `entries`, as for a hash function computed from the keys
+    /// of `entries` with `seed`.
+    #[doc(hidden)]
+    pub const unsafe fn from_raw_parts(
+        entries: &'static [(ConstCStr, V)],
+        seed: u64,
+        disps: &'static [(u32, u32)],
+        slots: &'static [u32],
+    ) -> ConstCStrMap<V> {
+        ConstCStrMap { entries, seed, disps, slots }
+    }
+
+    /// Returns the value corresponding to `key`, if there is one.
+    pub fn get(&self, key: &CStr) -> Option<&'static V> {
+        self.get_bytes(key.to_bytes())
+    }
+
+    /// Returns the value corresponding to the C string equal to `key`, if there is one.
+    pub fn get_str(&self, key: &str) -> Option<&'static V> {
+        self.get_bytes(key.as_bytes())
+    }
+
+    /// Returns the value corresponding to the C string at `ptr`, if there is one.
+    /// A null pointer never has a value.
+    ///
+    /// Safety
+    /// ------
+    /// `ptr` must be null or satisfy the requirements of `CStr::from_ptr()`.
+    pub unsafe fn get_ptr(&self, ptr: *const c_char) -> Option<&'static V> {
+        if ptr.is_null() {
+            return None;
+        }
+
+        self.get(CStr::from_ptr(ptr))
+    }
+
+    /// Returns the key equal to `key` and its value, if there is one.
+    pub fn get_key_value(&self, key: &CStr) -> Option<(ConstCStr, &'static V)> {
+        self.find(key.to_bytes()).map(|(key, value)| (*key, value))
+    }
+
+    /// Returns `true` if the map has a value for `key`.
+    pub fn contains_key(&self, key: &CStr) -> bool {
+        self.find(key.to_bytes()).is_some()
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the entries of the map, in the order they were given to the macro.
+    pub fn iter(&self) -> slice::Iter<'static, (ConstCStr, V)> {
+        self.entries.iter()
+    }
+
+    fn get_bytes(&self, key: &[u8]) -> Option<&'static V> {
+        self.find(key).map(|(_, value)| value)
+    }
+
+    /// Looks up the only entry that can have `key`, then checks whether it actually does.
+    fn find(&self, key: &[u8]) -> Option<&'static (ConstCStr, V)> {
+        let entries = self.entries;
+
+        if entries.is_empty() {
+            return None;
+        }
+
+        let hashes = phf::hash(self.seed, key);
+        let disps = self.disps[phf::bucket(hashes, entries.len())];
+        let entry = &entries[self.slots[phf::slot(hashes, disps, entries.len())] as usize];
+
+        if entry.0.to_bytes() == key {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+}
+
+impl<V: fmt::Debug + 'static> fmt::Debug for ConstCStrMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(self.entries.iter().map(|(key, value)| (key, value)))
+            .finish()
+    }
+}
+
+impl<V: 'static> IntoIterator for &ConstCStrMap<V> {
+    type Item = &'static (ConstCStr, V);
+    type IntoIter = slice::Iter<'static, (ConstCStr, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[test]
+fn test_map() {
+    static MAP: ConstCStrMap<u32> = const_cstr_map! {
+        "width" => 1,
+        "height" => 2,
+        "" => 3,
+    };
+
+    assert_eq!(MAP.len(), 3);
+    assert_eq!(MAP.get(const_cstr!("width").as_cstr()), Some(&1));
+    assert_eq!(MAP.get_str("height"), Some(&2));
+    assert_eq!(MAP.get_str(""), Some(&3));
+    assert_eq!(MAP.get_str("depth"), None);
+    assert_eq!(MAP.get_str("widt"), None);
+    assert_eq!(unsafe { MAP.get_ptr(const_cstr!("height").as_ptr()) }, Some(&2));
+    assert_eq!(unsafe { MAP.get_ptr(::std::ptr::null()) }, None);
+
+    let (key, _) = MAP.get_key_value(const_cstr!("width").as_cstr()).unwrap();
+    assert_eq!(key.to_str(), "width");
+
+    let keys: Vec<_> = MAP.iter().map(|&(key, _)| key.to_str()).collect();
+    assert_eq!(keys, ["width", "height", ""]);
+}
+
+#[test]
+fn test_map_many_keys() {
+    macro_rules! keys {
+        ($($n:expr)*) => (const_cstr_map! {
+            $(concat!("key", stringify!($n)) => $n,)*
+        });
+    }
+
+    static MAP: ConstCStrMap<u32> = keys!(
+        0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16 17 18 19 20 21 22 23 24 25 26 27 28 29
+        30 31 32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47 48 49 50 51 52 53 54 55 56 57
+        58 59 60 61 62 63 64 65 66 67 68 69 70 71 72 73 74 75 76 77 78 79 80 81 82 83 84 85
+        86 87 88 89 90 91 92 93 94 95 96 97 98 99
+    );
+
+    for n in 0..100 {
+        assert_eq!(MAP.get_str(&format!("key{}", n)), Some(&n));
+    }
+
+    assert_eq!(MAP.get_str("key100"), None);
+}
+
+#[test]
+fn test_empty_map() {
+    static MAP: ConstCStrMap<()> = const_cstr_map! {};
+
+    assert!(MAP.is_empty());
+    assert_eq!(MAP.get_str(""), None);
+}
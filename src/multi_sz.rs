@@ -0,0 +1,119 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Double-NUL `MULTI_SZ` string lists, built by the `const_multi_sz!` macro.
+use std::fmt;
+use std::iter::FusedIterator;
+use std::os::raw::c_char;
+
+use ConstCStr;
+
+/// A static list of NUL-terminated strings followed by an extra NUL byte, the "double-NUL"
+/// `MULTI_SZ` layout of Windows `REG_MULTI_SZ` registry values and of Win32 APIs such as
+/// `CreateServiceA()`'s `lpDependencies`, built at compile time by the `const_multi_sz!`
+/// macro.
+///
+/// An empty list is two NUL bytes, so that it is terminated by a double NUL like any other.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConstMultiSz {
+    val: &'static str,
+}
+
+impl ConstMultiSz {
+    /// Wraps `val`, which must be laid out as described above, with no empty entries.
+    pub(crate) const fn from_str_with_nuls(val: &'static str) -> ConstMultiSz {
+        ConstMultiSz { val }
+    }
+
+    /// Returns the whole list, **with** every NUL byte, including the final extra one.
+    pub const fn as_str_with_nuls(&self) -> &'static str {
+        self.val
+    }
+
+    /// Returns the whole list as bytes, **with** every NUL byte, such as for the `lpData`
+    /// and `cbData` of `RegSetValueExA()`.
+    pub const fn as_bytes_with_nuls(&self) -> &'static [u8] {
+        self.val.as_bytes()
+    }
+
+    /// Returns a pointer to the beginning of the list.
+    pub const fn as_ptr(&self) -> *const c_char {
+        self.val.as_ptr().cast()
+    }
+
+    /// Returns `true` if the list has no entries.
+    pub const fn is_empty(&self) -> bool {
+        self.val.as_bytes()[0] == 0
+    }
+
+    /// Iterates over the entries, in the order they were given to the macro.
+    pub fn entries(&self) -> Entries {
+        Entries { rest: self.val }
+    }
+}
+
+impl IntoIterator for ConstMultiSz {
+    type Item = ConstCStr;
+    type IntoIter = Entries;
+
+    fn into_iter(self) -> Entries {
+        self.entries()
+    }
+}
+
+impl fmt::Debug for ConstMultiSz {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.entries()).finish()
+    }
+}
+
+/// An iterator over the entries of a `ConstMultiSz`, created by `ConstMultiSz::entries()`.
+#[derive(Clone, Debug)]
+pub struct Entries {
+    rest: &'static str,
+}
+
+impl Iterator for Entries {
+    type Item = ConstCStr;
+
+    fn next(&mut self) -> Option<ConstCStr> {
+        let len = self.rest.find('\0')?;
+
+        if len == 0 {
+            return None;
+        }
+
+        let (entry, rest) = self.rest.split_at(len + 1);
+        self.rest = rest;
+        // Safe because `entry` runs up to and including the first NUL byte.
+        Some(unsafe { ConstCStr::from_str_with_nul_unchecked(entry) })
+    }
+}
+
+impl FusedIterator for Entries {}
+
+#[test]
+fn test_multi_sz() {
+    const DEPENDENCIES: ConstMultiSz = const_multi_sz!["Tcpip", const_cstr!("Afd"), "Dnscache"];
+    const EMPTY: ConstMultiSz = const_multi_sz![];
+
+    assert_eq!(DEPENDENCIES.as_str_with_nuls(), "Tcpip\0Afd\0Dnscache\0\0");
+    assert_eq!(
+        DEPENDENCIES.entries().map(|entry| entry.to_str()).collect::<Vec<_>>(),
+        ["Tcpip", "Afd", "Dnscache"]
+    );
+    assert_eq!(DEPENDENCIES.entries().nth(1).map(|entry| entry.as_ptr()), Some(unsafe {
+        DEPENDENCIES.as_ptr().add(6)
+    }));
+    assert_eq!(format!("{:?}", DEPENDENCIES), r#"["Tcpip", "Afd", "Dnscache"]"#);
+    assert!(!DEPENDENCIES.is_empty());
+
+    assert_eq!(EMPTY.as_bytes_with_nuls(), b"\0\0");
+    assert!(EMPTY.is_empty());
+    assert_eq!(EMPTY.into_iter().count(), 0);
+}
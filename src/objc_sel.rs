@@ -0,0 +1,75 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Cached Objective-C selectors, enabled by the `objc` feature on Apple platforms.
+use std::os::raw::{c_char, c_void};
+use std::fmt;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use ConstCStr;
+
+#[link(name = "objc")]
+extern "C" {
+    fn sel_registerName(name: *const c_char) -> *const c_void;
+}
+
+/// An Objective-C selector name along with its `SEL`, registered with the runtime on first
+/// use and cached after that, usually declared through the `const_sel!` macro.
+pub struct ConstSel {
+    name: ConstCStr,
+    sel: AtomicPtr<c_void>,
+}
+
+impl ConstSel {
+    /// Creates a selector for `name` that will be registered on first use.
+    pub const fn new(name: ConstCStr) -> ConstSel {
+        ConstSel { name, sel: AtomicPtr::new(ptr::null_mut()) }
+    }
+
+    /// Returns the `SEL`, registering it first if this is the first use.
+    ///
+    /// It has the layout of the `Sel` types of the `objc` and `objc2` crates, so it can be
+    /// transmuted to them.
+    pub fn as_ptr(&self) -> *const c_void {
+        let sel = self.sel.load(Ordering::Acquire);
+
+        if !sel.is_null() {
+            return sel;
+        }
+
+        // Safe because the name is a NUL-terminated string. Registering the same name always
+        // returns the same `SEL`, so threads racing to do so store the same pointer.
+        let sel = unsafe { sel_registerName(self.name.as_ptr()) };
+        self.sel.store(sel as *mut c_void, Ordering::Release);
+        sel
+    }
+
+    /// Returns the name of the selector.
+    pub const fn name(&self) -> ConstCStr {
+        self.name
+    }
+}
+
+impl fmt::Debug for ConstSel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ConstSel").field(&self.name).finish()
+    }
+}
+
+#[test]
+fn test_const_sel() {
+    static INIT: ConstSel = ConstSel::new(const_cstr!("init"));
+
+    let sel = INIT.as_ptr();
+
+    assert!(!sel.is_null());
+    assert_eq!(INIT.as_ptr(), sel);
+    assert_eq!(const_sel!("init"), sel);
+    assert_eq!(INIT.name().to_str(), "init");
+}
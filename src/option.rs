@@ -0,0 +1,64 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+use std::ptr;
+
+use {ConstCBytes, ConstCStr};
+
+/// Extension methods for optional C string constants, which C represents as a pointer that
+/// may be null.
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::{AsPtrOrNull, ConstCStr};
+///
+/// const_cstr! {
+///     TITLE = "Untitled";
+/// }
+/// # fn main() {
+/// let title: Option<ConstCStr> = Some(TITLE);
+/// // e.g. `gtk_window_set_title(window, title.as_ptr_or_null())`
+/// assert_eq!(title.as_ptr_or_null(), TITLE.as_ptr());
+/// assert!(None::<ConstCStr>.as_ptr_or_null().is_null());
+/// # }
+/// ```
+pub trait AsPtrOrNull {
+    /// Returns a pointer to the string if there is one, or a null pointer otherwise.
+    fn as_ptr_or_null(&self) -> *const c_char;
+}
+
+impl AsPtrOrNull for Option<ConstCStr> {
+    fn as_ptr_or_null(&self) -> *const c_char {
+        match *self {
+            Some(cstr) => cstr.as_ptr(),
+            None => ptr::null(),
+        }
+    }
+}
+
+impl AsPtrOrNull for Option<ConstCBytes> {
+    fn as_ptr_or_null(&self) -> *const c_char {
+        match *self {
+            Some(bytes) => bytes.as_ptr(),
+            None => ptr::null(),
+        }
+    }
+}
+
+#[test]
+fn test_as_ptr_or_null() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    assert_eq!(Some(HELLO).as_ptr_or_null(), HELLO.as_ptr());
+    assert!(None::<ConstCStr>.as_ptr_or_null().is_null());
+    assert_eq!(Some(const_cstr!(b"\xFF")).as_ptr_or_null(), const_cstr!(b"\xFF").as_ptr());
+    assert!(None::<ConstCBytes>.as_ptr_or_null().is_null());
+}
@@ -0,0 +1,266 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Minimal perfect hashing, computed during `const` evaluation.
+//!
+//! This is the "hash, displace and compress" scheme also used by the `phf` crate, without
+//! the compression: every key is hashed into a bucket, and each bucket is assigned a pair
+//! of displacements that sends all of its keys to distinct, otherwise unused slots of a
+//! table exactly as long as the set of keys. A lookup is then one hash, two array reads
+//! and a single comparison against the only key that could possibly match.
+
+use checksum::{fnv1a, FNV_OFFSET_BASIS};
+use ConstCStr;
+use __private::Message;
+
+/// The perfect hash function for a set of `N` keys.
+pub struct Index<const N: usize> {
+    /// The seed the hash function was computed with.
+    pub seed: u64,
+    /// The displacements assigned to each bucket.
+    pub disps: [(u32, u32); N],
+    /// The index of the key occupying each slot.
+    pub slots: [u32; N],
+}
+
+impl<const N: usize> Index<N> {
+    /// Computes a perfect hash function for `keys`.
+    ///
+    /// Panics
+    /// ------
+    /// If `N` is not `keys.len()` or if `keys` contains duplicates.
+    pub const fn build(keys: &[ConstCStr]) -> Index<N> {
+        if keys.len() != N {
+            panic!("Index length must be the number of keys");
+        }
+
+        if N > u32::MAX as usize {
+            panic!("too many keys");
+        }
+
+        let mut seed = 0;
+
+        loop {
+            if let Some(index) = Index::try_build(keys, seed) {
+                return index;
+            }
+
+            seed += 1;
+        }
+    }
+
+    /// Tries to compute a perfect hash function for `keys` with the given seed.
+    const fn try_build(keys: &[ConstCStr], seed: u64) -> Option<Index<N>> {
+        let mut hashes = [Hashes { g: 0, f1: 0, f2: 0 }; N];
+        let mut bucket_lens = [0usize; N];
+        let mut i = 0;
+
+        while i < N {
+            hashes[i] = hash(seed, keys[i].to_bytes());
+            bucket_lens[bucket(hashes[i], N)] += 1;
+            i += 1;
+        }
+
+        // Group key indices by bucket, with each bucket's keys starting at
+        // `bucket_starts[bucket]`.
+        let mut bucket_starts = [0usize; N];
+        let mut max_bucket_len = 0;
+        let mut start = 0;
+        i = 0;
+
+        while i < N {
+            bucket_starts[i] = start;
+            start += bucket_lens[i];
+
+            if bucket_lens[i] > max_bucket_len {
+                max_bucket_len = bucket_lens[i];
+            }
+
+            i += 1;
+        }
+
+        let mut keys_by_bucket = [0usize; N];
+        let mut filled = [0usize; N];
+        i = 0;
+
+        while i < N {
+            let bucket = bucket(hashes[i], N);
+            keys_by_bucket[bucket_starts[bucket] + filled[bucket]] = i;
+            filled[bucket] += 1;
+            i += 1;
+        }
+
+        let mut index = Index { seed, disps: [(0, 0); N], slots: [0; N] };
+        let mut occupied = [false; N];
+        // Placing the largest buckets first, while the table is still mostly empty, is what
+        // makes this converge quickly.
+        let mut bucket_len = max_bucket_len;
+
+        while bucket_len > 0 {
+            let mut bucket = 0;
+
+            while bucket < N {
+                if bucket_lens[bucket] == bucket_len {
+                    let bucket_keys = split(&keys_by_bucket, bucket_starts[bucket], bucket_len);
+
+                    match place(keys, &hashes, bucket_keys, &occupied) {
+                        Some(disps) => {
+                            index.disps[bucket] = disps;
+
+                            let mut j = 0;
+
+                            while j < bucket_len {
+                                let slot = slot(hashes[bucket_keys[j]], disps, N);
+                                occupied[slot] = true;
+                                index.slots[slot] = bucket_keys[j] as u32;
+                                j += 1;
+                            }
+                        }
+                        None => return None,
+                    }
+                }
+
+                bucket += 1;
+            }
+
+            bucket_len -= 1;
+        }
+
+        Some(index)
+    }
+}
+
+/// Finds displacements sending all of `bucket_keys` to distinct unoccupied slots.
+///
+/// Panics if two of the keys are equal, since no displacements can ever separate them.
+const fn place<const N: usize>(
+    keys: &[ConstCStr],
+    hashes: &[Hashes; N],
+    bucket_keys: &[usize],
+    occupied: &[bool; N],
+) -> Option<(u32, u32)> {
+    let mut d1 = 0;
+
+    while d1 < N {
+        let mut d2 = 0;
+
+        'disps: while d2 < N {
+            let disps = (d1 as u32, d2 as u32);
+            let mut i = 0;
+
+            while i < bucket_keys.len() {
+                let slot = slot(hashes[bucket_keys[i]], disps, N);
+
+                if occupied[slot] {
+                    d2 += 1;
+                    continue 'disps;
+                }
+
+                let mut j = 0;
+
+                while j < i {
+                    if slot == self::slot(hashes[bucket_keys[j]], disps, N) {
+                        let a = keys[bucket_keys[i]].to_bytes();
+                        let b = keys[bucket_keys[j]].to_bytes();
+
+                        if eq(a, b) {
+                            // Safe because `a` is the contents of a `str` minus the NUL.
+                            let key = unsafe { ::std::str::from_utf8_unchecked(a) };
+
+                            Message::new()
+                                .push_str("duplicate key \"")
+                                .push_str(key)
+                                .push_str("\"")
+                                .panic();
+                        }
+
+                        d2 += 1;
+                        continue 'disps;
+                    }
+
+                    j += 1;
+                }
+
+                i += 1;
+            }
+
+            return Some(disps);
+        }
+
+        d1 += 1;
+    }
+
+    None
+}
+
+/// The hashes of a key that determine its bucket (`g`) and, together with its bucket's
+/// displacements, its slot (`f1` and `f2`).
+#[derive(Copy, Clone)]
+pub struct Hashes {
+    g: u32,
+    f1: u32,
+    f2: u32,
+}
+
+/// Hashes `bytes` with 64-bit FNV-1a, seeded by first hashing in the bytes of `seed`, then
+/// spreads the result across all three hashes.
+pub const fn hash(seed: u64, bytes: &[u8]) -> Hashes {
+    let h = fnv1a(fnv1a(FNV_OFFSET_BASIS, &seed.to_le_bytes()), bytes);
+
+    let h1 = mix(h);
+    let h2 = mix(h ^ 0x9e37_79b9_7f4a_7c15);
+
+    Hashes { g: (h1 >> 32) as u32, f1: h1 as u32, f2: h2 as u32 }
+}
+
+/// The SplitMix64 finalizer, since FNV-1a alone mixes the high bits poorly.
+const fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// The bucket of a key with the given hashes, in a table of `len` slots.
+pub const fn bucket(hashes: Hashes, len: usize) -> usize {
+    hashes.g as usize % len
+}
+
+/// The slot of a key with the given hashes and bucket displacements, in a table of `len`
+/// slots.
+pub const fn slot(hashes: Hashes, (d1, d2): (u32, u32), len: usize) -> usize {
+    let slot = (hashes.f1 as u64)
+        .wrapping_add((d1 as u64).wrapping_mul(hashes.f2 as u64))
+        .wrapping_add(d2 as u64);
+
+    (slot % len as u64) as usize
+}
+
+/// `&array[start..start + len]`, since range indexing is not available in `const fn`s.
+const fn split(array: &[usize], start: usize, len: usize) -> &[usize] {
+    let (_, rest) = array.split_at(start);
+    let (slice, _) = rest.split_at(len);
+    slice
+}
+
+const fn eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+
+        i += 1;
+    }
+
+    true
+}
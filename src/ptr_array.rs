@@ -0,0 +1,112 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+use std::fmt;
+use std::iter::Copied;
+use std::slice;
+
+use ConstCStr;
+
+/// A list of C strings along with a NULL-terminated array of pointers to them, as taken by
+/// `execv()`, Vulkan instance creation and GTK-style APIs, built at compile time by the
+/// `const_cstr_ptr_array!` macro.
+///
+/// Unlike the array of pointers built by `const_argv!`, this can be stored in a `static`.
+#[derive(Copy, Clone)]
+pub struct ConstCStrArray {
+    strs: &'static [ConstCStr],
+    /// A pointer to each of `strs`, followed by a null pointer.
+    ptrs: &'static [*const c_char],
+}
+
+// Safe because the pointers only point to the `'static`, immutable strings in `strs`.
+unsafe impl Send for ConstCStrArray {}
+unsafe impl Sync for ConstCStrArray {}
+
+impl ConstCStrArray {
+    /// Not intended to be called directly. Public only to allow initialization by the
+    /// `const_cstr_ptr_array!` macro.
+    ///
+    /// Safety
+    /// ------
+    /// `ptrs` must hold a pointer to each of `strs`, in order, followed by a null pointer.
+    #[doc(hidden)]
+    pub const unsafe fn from_raw_parts(
+        strs: &'static [ConstCStr],
+        ptrs: &'static [*const c_char],
+    ) -> ConstCStrArray {
+        ConstCStrArray { strs, ptrs }
+    }
+
+    /// Returns a pointer to the NULL-terminated array of pointers to the strings.
+    pub const fn as_ptr(&self) -> *const *const c_char {
+        self.ptrs.as_ptr()
+    }
+
+    /// Returns the array of pointers to the strings, **with** the null pointer at the end.
+    pub const fn as_ptrs_with_null(&self) -> &'static [*const c_char] {
+        self.ptrs
+    }
+
+    /// Returns the strings.
+    pub const fn as_slice(&self) -> &'static [ConstCStr] {
+        self.strs
+    }
+
+    /// Returns the number of strings, **without** the null pointer at the end, such as for
+    /// an `argc` or an `enabledExtensionCount`.
+    pub const fn len(&self) -> usize {
+        self.strs.len()
+    }
+
+    /// Returns `true` if there are no strings, only the null pointer at the end.
+    pub const fn is_empty(&self) -> bool {
+        self.strs.is_empty()
+    }
+
+    /// Iterates over the strings, in the order they were given to the macro.
+    pub fn iter(&self) -> Copied<slice::Iter<'static, ConstCStr>> {
+        self.strs.iter().copied()
+    }
+}
+
+impl IntoIterator for ConstCStrArray {
+    type Item = ConstCStr;
+    type IntoIter = Copied<slice::Iter<'static, ConstCStr>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for ConstCStrArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.strs).finish()
+    }
+}
+
+#[test]
+fn test_ptr_array() {
+    use std::ffi::CStr;
+
+    static ARGV: ConstCStrArray = const_cstr_ptr_array!["ls", concat!("-", "l")];
+    const EMPTY: ConstCStrArray = const_cstr_ptr_array![];
+
+    assert_eq!(ARGV.len(), 2);
+    assert_eq!(ARGV.as_ptrs_with_null().len(), 3);
+    assert!(ARGV.as_ptrs_with_null()[2].is_null());
+    assert_eq!(ARGV.as_slice()[1].to_str(), "-l");
+    assert_eq!(ARGV.as_ptrs_with_null()[1], ARGV.as_slice()[1].as_ptr());
+    assert_eq!(unsafe { CStr::from_ptr(*ARGV.as_ptr()) }.to_str(), Ok("ls"));
+    assert_eq!(ARGV.iter().map(|arg| arg.to_str()).collect::<Vec<_>>(), ["ls", "-l"]);
+    assert_eq!(format!("{:?}", ARGV), r#"["ls", "-l"]"#);
+
+    assert!(EMPTY.is_empty());
+    assert!(unsafe { *EMPTY.as_ptr() }.is_null());
+}
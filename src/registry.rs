@@ -0,0 +1,102 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+use std::ffi::CStr;
+use std::fmt;
+
+use {ConstCStr, ConstCStrMap};
+
+/// A registry of named C string `static`s, declared along with them by the
+/// `const_cstr_registry!` macro, which maps strings handed back by C to the canonical
+/// constant with the same contents.
+///
+/// Lookups cost the same as those of `ConstCStrMap`, with no allocation.
+pub struct ConstCStrRegistry {
+    map: ConstCStrMap<&'static ConstCStr>,
+}
+
+impl ConstCStrRegistry {
+    /// Not intended to be called directly. Public only to allow initialization by the
+    /// `const_cstr_registry!` macro.
+    #[doc(hidden)]
+    pub const fn from_map(map: ConstCStrMap<&'static ConstCStr>) -> ConstCStrRegistry {
+        ConstCStrRegistry { map }
+    }
+
+    /// Returns the registered constant equal to `key`, if there is one.
+    pub fn lookup(&self, key: &CStr) -> Option<ConstCStr> {
+        self.map.get(key).map(|&&cstr| cstr)
+    }
+
+    /// Returns the registered constant equal to `key`, if there is one.
+    pub fn lookup_str(&self, key: &str) -> Option<ConstCStr> {
+        self.map.get_str(key).map(|&&cstr| cstr)
+    }
+
+    /// Returns the registered constant equal to the C string at `ptr`, if there is one.
+    /// A null pointer is never registered.
+    ///
+    /// Safety
+    /// ------
+    /// `ptr` must be null or satisfy the requirements of `CStr::from_ptr()`.
+    pub unsafe fn lookup_ptr(&self, ptr: *const c_char) -> Option<ConstCStr> {
+        self.map.get_ptr(ptr).map(|&&cstr| cstr)
+    }
+
+    /// Returns the number of registered constants.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if no constants are registered.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterates over the registered constants, in the order they were declared.
+    pub fn iter(&self) -> impl Iterator<Item = ConstCStr> {
+        self.map.iter().map(|&(_, &cstr)| cstr)
+    }
+}
+
+impl fmt::Debug for ConstCStrRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test_const_cstr_registry_mod {
+    const_cstr_registry! {
+        pub mod properties {
+            pub WIDTH = "width";
+            /// Documented.
+            pub HEIGHT = "height";
+        }
+    }
+}
+
+#[test]
+fn test_registry() {
+    use self::test_const_cstr_registry_mod::properties::{self, HEIGHT, REGISTRY, WIDTH};
+
+    // A copy of the string, as if handed back by C.
+    let from_c = ::std::ffi::CString::new("height").unwrap();
+    let found = unsafe { REGISTRY.lookup_ptr(from_c.as_ptr()) }.unwrap();
+
+    assert_eq!(found.as_ptr(), HEIGHT.as_ptr());
+    assert_eq!(REGISTRY.lookup(WIDTH.as_cstr()).map(|c| c.as_ptr()), Some(WIDTH.as_ptr()));
+    assert_eq!(REGISTRY.lookup_str("width").map(|c| c.as_ptr()), Some(WIDTH.as_ptr()));
+    assert_eq!(REGISTRY.lookup_str("depth"), None);
+    assert!(unsafe { REGISTRY.lookup_ptr(::std::ptr::null()) }.is_none());
+    assert_eq!(
+        properties::REGISTRY.iter().map(|c| c.to_str()).collect::<Vec<_>>(),
+        ["width", "height"]
+    );
+}
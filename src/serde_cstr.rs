@@ -0,0 +1,127 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! `serde` support, enabled by the `serde` feature.
+//!
+//! `ConstCStr` serializes as its string and `ConstCBytes` as its bytes, both without the NUL
+//! terminator. Neither can be deserialized, since they only ever wrap `'static` data, so
+//! fields that are usually a constant but may be read from a config file should be a
+//! `Cow<'static, CStr>` instead, (de)serialized with this module:
+//!
+//! ```rust
+//! # #[macro_use] extern crate const_cstr;
+//! # extern crate serde_derive;
+//! # extern crate serde_json;
+//! use std::borrow::Cow;
+//! use std::ffi::CStr;
+//!
+//! const_cstr! {
+//!     DEFAULT_DEVICE = "/dev/ttyUSB0";
+//! }
+//!
+//! #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+//! struct Config {
+//!     #[serde(with = "const_cstr::serde_cstr", default = "default_device")]
+//!     device: Cow<'static, CStr>,
+//! }
+//!
+//! fn default_device() -> Cow<'static, CStr> {
+//!     DEFAULT_DEVICE.into()
+//! }
+//! # fn main() {
+//! let config: Config = serde_json::from_str("{}").unwrap();
+//! assert_eq!(config.device, DEFAULT_DEVICE.as_cstr());
+//!
+//! let config: Config = serde_json::from_str(r#"{"device": "/dev/ttyACM0"}"#).unwrap();
+//! assert_eq!(serde_json::to_string(&config).unwrap(), r#"{"device":"/dev/ttyACM0"}"#);
+//! # }
+//! ```
+use std::borrow::Cow;
+use std::ffi::{CStr, CString};
+use std::fmt;
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+
+use {ConstCBytes, ConstCStr};
+
+impl Serialize for ConstCStr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.to_str())
+    }
+}
+
+impl Serialize for ConstCBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.to_bytes())
+    }
+}
+
+/// Serializes a C string as a string, without the NUL terminator.
+///
+/// Fails if the C string is not valid UTF-8.
+pub fn serialize<S: Serializer>(cstr: &CStr, serializer: S) -> Result<S::Ok, S::Error> {
+    match cstr.to_str() {
+        Ok(s) => serializer.serialize_str(s),
+        Err(_) => Err(ser::Error::custom("C string is not valid UTF-8")),
+    }
+}
+
+/// Deserializes a string into an owned C string, appending the NUL terminator.
+///
+/// Fails if the string contains a NUL byte.
+pub fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Cow<'static, CStr>, D::Error> {
+    deserializer.deserialize_string(CStringVisitor).map(Cow::Owned)
+}
+
+struct CStringVisitor;
+
+impl<'de> Visitor<'de> for CStringVisitor {
+    type Value = CString;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a string without NUL bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<CString, E> {
+        self.visit_string(s.to_owned())
+    }
+
+    fn visit_string<E: de::Error>(self, s: String) -> Result<CString, E> {
+        CString::new(s).map_err(|err| {
+            E::custom(format_args!(
+                "C string contains an interior NUL byte at index {}",
+                err.nul_position()
+            ))
+        })
+    }
+}
+
+#[test]
+fn test_serialize() {
+    const_cstr! {
+        HELLO = "Hello";
+    }
+
+    assert_eq!(::serde_json::to_string(&HELLO).unwrap(), r#""Hello""#);
+    assert_eq!(::serde_json::to_string(&const_cstr!(b"\xFF")).unwrap(), "[255]");
+}
+
+#[test]
+fn test_deserialize() {
+    let mut de = ::serde_json::Deserializer::from_str(r#""Hello""#);
+    assert_eq!(deserialize(&mut de).unwrap(), const_cstr!("Hello").as_cstr());
+
+    let mut de = ::serde_json::Deserializer::from_str(r#""a\u0000""#);
+    assert_eq!(
+        deserialize(&mut de).unwrap_err().to_string(),
+        "C string contains an interior NUL byte at index 1 at line 1 column 9"
+    );
+}
@@ -0,0 +1,99 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+use std::ffi::CStr;
+use std::fmt;
+
+use {ConstCStr, ConstCStrMap};
+
+/// A set of C strings, built at compile time by the `const_cstr_set!` macro.
+///
+/// Membership tests cost a single hash of the key and at most one string comparison,
+/// with no allocation. See `ConstCStrMap`.
+pub struct ConstCStrSet {
+    map: ConstCStrMap<()>,
+}
+
+impl ConstCStrSet {
+    /// Not intended to be called directly. Public only to allow initialization by the
+    /// `const_cstr_set!` macro.
+    #[doc(hidden)]
+    pub const fn from_map(map: ConstCStrMap<()>) -> ConstCStrSet {
+        ConstCStrSet { map }
+    }
+
+    /// Returns `true` if `key` is in the set.
+    pub fn contains(&self, key: &CStr) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Returns `true` if the C string equal to `key` is in the set.
+    pub fn contains_str(&self, key: &str) -> bool {
+        self.map.get_str(key).is_some()
+    }
+
+    /// Returns `true` if the C string at `ptr` is in the set.
+    /// A null pointer is never in the set.
+    ///
+    /// Safety
+    /// ------
+    /// `ptr` must be null or satisfy the requirements of `CStr::from_ptr()`.
+    pub unsafe fn contains_ptr(&self, ptr: *const c_char) -> bool {
+        self.map.get_ptr(ptr).is_some()
+    }
+
+    /// Returns the member of the set equal to `key`, if there is one.
+    pub fn get(&self, key: &CStr) -> Option<ConstCStr> {
+        self.map.get_key_value(key).map(|(key, _)| key)
+    }
+
+    /// Returns the number of C strings in the set.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Iterates over the C strings in the set, in the order they were given to the macro.
+    pub fn iter(&self) -> impl Iterator<Item = ConstCStr> {
+        self.map.iter().map(|&(key, _)| key)
+    }
+}
+
+impl fmt::Debug for ConstCStrSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[test]
+fn test_set() {
+    static EXTENSIONS: ConstCStrSet = const_cstr_set! {
+        "GL_ARB_sync",
+        "GL_ARB_debug_output",
+    };
+
+    assert_eq!(EXTENSIONS.len(), 2);
+    assert!(EXTENSIONS.contains(const_cstr!("GL_ARB_sync").as_cstr()));
+    assert!(EXTENSIONS.contains_str("GL_ARB_debug_output"));
+    assert!(!EXTENSIONS.contains_str("GL_ARB_robustness"));
+    assert!(unsafe { EXTENSIONS.contains_ptr(const_cstr!("GL_ARB_sync").as_ptr()) });
+    assert!(!unsafe { EXTENSIONS.contains_ptr(::std::ptr::null()) });
+    assert_eq!(
+        EXTENSIONS.get(const_cstr!("GL_ARB_sync").as_cstr()).map(|key| key.to_str()),
+        Some("GL_ARB_sync")
+    );
+    assert_eq!(
+        EXTENSIONS.iter().map(|key| key.to_str()).collect::<Vec<_>>(),
+        ["GL_ARB_sync", "GL_ARB_debug_output"]
+    );
+}
@@ -0,0 +1,203 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+use std::borrow::Borrow;
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+use InteriorNulError;
+
+/// A C string built at runtime, stored inline if it is short enough and on the heap
+/// otherwise, for passing strings from Rust to C without allocating in the common case:
+///
+/// ```rust
+/// use const_cstr::SmallCStr;
+///
+/// let name = SmallCStr::new("eth0").unwrap();
+/// // e.g. `libc::if_nametoindex(name.as_ptr())`
+/// assert!(name.is_inline());
+/// assert_eq!(name.to_bytes_with_nul(), b"eth0\0");
+/// ```
+#[derive(Clone)]
+pub struct SmallCStr {
+    repr: Repr,
+}
+
+#[derive(Clone)]
+enum Repr {
+    /// The contents followed by a NUL byte, then unused bytes.
+    Inline { buf: [u8; SmallCStr::INLINE_CAPACITY], len: usize },
+    Heap(CString),
+}
+
+impl SmallCStr {
+    /// The number of bytes stored inline, **with** the NUL terminating byte.
+    pub const INLINE_CAPACITY: usize = 64;
+
+    /// Copies `s` and appends a NUL terminating byte.
+    ///
+    /// Errors
+    /// ------
+    /// If `s` contains a NUL byte.
+    pub fn new(s: &str) -> Result<SmallCStr, InteriorNulError> {
+        SmallCStr::from_bytes(s.as_bytes())
+    }
+
+    /// Like `new()`, but for bytes which need not be valid UTF-8.
+    pub fn from_bytes(bytes: &[u8]) -> Result<SmallCStr, InteriorNulError> {
+        if let Some(position) = bytes.iter().position(|&b| b == 0) {
+            return Err(InteriorNulError { position });
+        }
+
+        let repr = if bytes.len() < SmallCStr::INLINE_CAPACITY {
+            let mut buf = [0; SmallCStr::INLINE_CAPACITY];
+            buf[..bytes.len()].copy_from_slice(bytes);
+            Repr::Inline { buf, len: bytes.len() }
+        } else {
+            // Safe because of the above check.
+            Repr::Heap(unsafe { CString::from_vec_unchecked(bytes.to_vec()) })
+        };
+
+        Ok(SmallCStr { repr })
+    }
+
+    /// Returns the string as a `CStr`.
+    pub fn as_cstr(&self) -> &CStr {
+        match self.repr {
+            // Safe because `from_bytes()` checked for NUL bytes and the buffer has a NUL
+            // byte after the contents.
+            Repr::Inline { ref buf, len } => unsafe {
+                CStr::from_bytes_with_nul_unchecked(&buf[..len + 1])
+            },
+            Repr::Heap(ref cstring) => cstring,
+        }
+    }
+
+    /// Returns a pointer to the beginning of the string, valid for as long as `self` is and
+    /// is not moved.
+    pub fn as_ptr(&self) -> *const c_char {
+        self.as_cstr().as_ptr()
+    }
+
+    /// Returns the string as a byte slice, **without** the NUL terminating byte.
+    pub fn to_bytes(&self) -> &[u8] {
+        self.as_cstr().to_bytes()
+    }
+
+    /// Returns the string as a byte slice, **with** the NUL terminating byte.
+    pub fn to_bytes_with_nul(&self) -> &[u8] {
+        self.as_cstr().to_bytes_with_nul()
+    }
+
+    /// Returns the length of the string in bytes, **without** the NUL terminating byte.
+    pub fn len(&self) -> usize {
+        match self.repr {
+            Repr::Inline { len, .. } => len,
+            Repr::Heap(ref cstring) => cstring.as_bytes().len(),
+        }
+    }
+
+    /// Returns `true` if the string has no bytes before the NUL terminating byte.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the string is stored inline, rather than on the heap.
+    pub fn is_inline(&self) -> bool {
+        match self.repr {
+            Repr::Inline { .. } => true,
+            Repr::Heap(_) => false,
+        }
+    }
+}
+
+impl Deref for SmallCStr {
+    type Target = CStr;
+
+    fn deref(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+impl AsRef<CStr> for SmallCStr {
+    fn as_ref(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+impl Borrow<CStr> for SmallCStr {
+    fn borrow(&self) -> &CStr {
+        self.as_cstr()
+    }
+}
+
+/// Like `SmallCStr::new()`.
+impl<'a> TryFrom<&'a str> for SmallCStr {
+    type Error = InteriorNulError;
+
+    fn try_from(s: &'a str) -> Result<SmallCStr, InteriorNulError> {
+        SmallCStr::new(s)
+    }
+}
+
+impl From<SmallCStr> for CString {
+    fn from(small: SmallCStr) -> CString {
+        match small.repr {
+            Repr::Inline { .. } => small.as_cstr().to_owned(),
+            Repr::Heap(cstring) => cstring,
+        }
+    }
+}
+
+impl fmt::Debug for SmallCStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_cstr(), f)
+    }
+}
+
+impl PartialEq for SmallCStr {
+    fn eq(&self, other: &SmallCStr) -> bool {
+        self.as_cstr() == other.as_cstr()
+    }
+}
+
+impl Eq for SmallCStr {}
+
+impl Hash for SmallCStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_cstr().hash(state)
+    }
+}
+
+#[test]
+fn test_small_cstr() {
+    let short = SmallCStr::new("/dev/null").unwrap();
+    let max_inline = "x".repeat(SmallCStr::INLINE_CAPACITY - 1);
+    let long = "x".repeat(SmallCStr::INLINE_CAPACITY);
+
+    assert!(short.is_inline());
+    assert_eq!(short.to_bytes_with_nul(), b"/dev/null\0");
+    assert_eq!(short.len(), 9);
+    assert_eq!(unsafe { CStr::from_ptr(short.as_ptr()) }.to_str(), Ok("/dev/null"));
+
+    assert!(SmallCStr::new(&max_inline).unwrap().is_inline());
+    assert_eq!(SmallCStr::new(&max_inline).unwrap().to_bytes(), max_inline.as_bytes());
+
+    let spilled = SmallCStr::new(&long).unwrap();
+    assert!(!spilled.is_inline());
+    assert_eq!(spilled.len(), long.len());
+    assert_eq!(CString::from(spilled).as_bytes(), long.as_bytes());
+
+    assert!(SmallCStr::new("").unwrap().is_empty());
+    assert_eq!(SmallCStr::from_bytes(b"\xFF").unwrap().to_bytes(), b"\xFF");
+    assert_eq!(SmallCStr::new("a\0b"), Err(InteriorNulError { position: 1 }));
+}
@@ -0,0 +1,68 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Constant-time comparisons, enabled by the `subtle` feature.
+//!
+//! For secrets such as API tokens compiled into the binary, whose contents must not leak
+//! through how long it takes to compare them to untrusted input. Only the length of the
+//! input compared to that of the secret can leak.
+use std::ffi::CStr;
+
+use subtle::{Choice, ConstantTimeEq};
+
+use {ConstCBytes, ConstCStr};
+
+impl ConstCStr {
+    /// Compares the wrapped string, without the NUL terminating byte, to `other` in
+    /// constant time, for as long as they are the same length.
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// const_cstr! {
+    ///     TOKEN = "s3cr3t";
+    /// }
+    /// # fn main() {
+    /// assert!(bool::from(TOKEN.ct_eq(b"s3cr3t")));
+    /// assert!(!bool::from(TOKEN.ct_eq(b"s3cr3T")));
+    /// # }
+    /// ```
+    pub fn ct_eq(&self, other: &[u8]) -> Choice {
+        self.to_bytes().ct_eq(other)
+    }
+
+    /// Like `ct_eq()`, but for a `CStr`, such as one handed to a callback by C.
+    pub fn ct_eq_cstr(&self, other: &CStr) -> Choice {
+        self.ct_eq(other.to_bytes())
+    }
+}
+
+impl ConstCBytes {
+    /// Compares the wrapped string, without the NUL terminating byte, to `other` in
+    /// constant time, for as long as they are the same length.
+    pub fn ct_eq(&self, other: &[u8]) -> Choice {
+        self.to_bytes().ct_eq(other)
+    }
+
+    /// Like `ct_eq()`, but for a `CStr`, such as one handed to a callback by C.
+    pub fn ct_eq_cstr(&self, other: &CStr) -> Choice {
+        self.ct_eq(other.to_bytes())
+    }
+}
+
+#[test]
+fn test_ct_eq() {
+    const_cstr! {
+        TOKEN = "s3cr3t";
+    }
+
+    assert!(bool::from(TOKEN.ct_eq(b"s3cr3t")));
+    assert!(!bool::from(TOKEN.ct_eq(b"s3cr3t\0")));
+    assert!(!bool::from(TOKEN.ct_eq(b"s3cr")));
+    assert!(bool::from(TOKEN.ct_eq_cstr(const_cstr!("s3cr3t").as_cstr())));
+    assert!(!bool::from(const_cstr!(b"\xFF").ct_eq_cstr(TOKEN.as_cstr())));
+}
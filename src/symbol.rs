@@ -0,0 +1,80 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Symbol lookup through `dlsym()` on Unix and `GetProcAddress()` on Windows, for code not
+//! using `libloading`.
+use std::os::raw::{c_char, c_void};
+
+use ConstCStr;
+
+#[cfg(unix)]
+#[cfg_attr(target_os = "linux", link(name = "dl"))]
+extern "C" {
+    #[link_name = "dlsym"]
+    fn c_dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+}
+
+#[cfg(windows)]
+#[link(name = "kernel32")]
+extern "system" {
+    #[link_name = "GetProcAddress"]
+    fn c_get_proc_address(module: *mut c_void, proc_name: *const c_char) -> *mut c_void;
+}
+
+impl ConstCStr {
+    /// Looks up the symbol named by the wrapped string with `dlsym()`, returning its
+    /// address, or a null pointer if it was not found.
+    ///
+    /// ```rust,ignore
+    /// # #[macro_use] extern crate const_cstr;
+    /// let handle = libc::dlopen(const_cstr!("libz.so.1").as_ptr(), libc::RTLD_NOW);
+    /// let zlib_version = const_cstr!("zlibVersion").dlsym(handle);
+    /// ```
+    ///
+    /// Safety
+    /// ------
+    /// `handle` must be a handle returned by `dlopen()` that has not been closed, or one of
+    /// the pseudo-handles `RTLD_DEFAULT` and `RTLD_NEXT`.
+    #[cfg(unix)]
+    pub unsafe fn dlsym(&self, handle: *mut c_void) -> *mut c_void {
+        c_dlsym(handle, self.as_ptr())
+    }
+
+    /// Looks up the function named by the wrapped string with `GetProcAddress()`, returning
+    /// its address, or a null pointer if it was not found.
+    ///
+    /// ```rust,ignore
+    /// # #[macro_use] extern crate const_cstr;
+    /// let kernel32 = GetModuleHandleA(const_cstr!("kernel32.dll").as_pcstr());
+    /// let get_tick_count = const_cstr!("GetTickCount64").get_proc_address(kernel32 as _);
+    /// ```
+    ///
+    /// Safety
+    /// ------
+    /// `module` must be an `HMODULE` of a module that is still loaded.
+    #[cfg(windows)]
+    pub unsafe fn get_proc_address(&self, module: *mut c_void) -> *mut c_void {
+        c_get_proc_address(module, self.as_ptr())
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_dlsym() {
+    // `RTLD_DEFAULT` on Linux.
+    let default = ::std::ptr::null_mut();
+
+    let strlen = unsafe { const_cstr!("strlen").dlsym(default) };
+    assert!(!strlen.is_null());
+
+    let strlen: unsafe extern "C" fn(*const c_char) -> usize =
+        unsafe { ::std::mem::transmute(strlen) };
+    assert_eq!(unsafe { strlen(const_cstr!("four").as_ptr()) }, 4);
+
+    assert!(unsafe { const_cstr!("const_cstr_no_such_symbol").dlsym(default) }.is_null());
+}
@@ -0,0 +1,127 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Conversions to `OsStr` and `Path`, on Unix.
+//!
+//! On Unix, the contents of a C string are exactly the bytes of the equivalent `OsStr`, so
+//! one path constant can be handed both to `std::fs` APIs and to C functions like `open()`.
+//!
+//! With the `unix` feature, this also provides `openlog()`.
+use std::ffi::OsStr;
+#[cfg(feature = "unix")]
+use std::os::raw::{c_char, c_int};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+use {ConstCBytes, ConstCStr};
+
+#[cfg(feature = "unix")]
+extern "C" {
+    #[link_name = "openlog"]
+    fn c_openlog(ident: *const c_char, option: c_int, facility: c_int);
+}
+
+/// Opens the connection to the system logger with `ident` as the name to prefix messages
+/// with, by calling `openlog()`.
+///
+/// `openlog()` keeps the `ident` pointer rather than copying the string, and uses it for
+/// every later `syslog()` call, so it must stay valid for the rest of the process. Passing
+/// the pointer of a `CString` that is later dropped leaves the logger reading freed memory;
+/// a `ConstCStr` is `'static`, so this is safe.
+///
+/// `option` and `facility` are passed as is, such as `libc::LOG_PID | libc::LOG_NDELAY` and
+/// `libc::LOG_DAEMON`.
+///
+/// ```rust,no_run
+/// # #[macro_use] extern crate const_cstr;
+/// # fn main() {
+/// # const LOG_PID: std::os::raw::c_int = 0x01;
+/// # const LOG_DAEMON: std::os::raw::c_int = 3 << 3;
+/// // e.g. with `libc::LOG_PID` and `libc::LOG_DAEMON`
+/// const_cstr::openlog(const_cstr!("my-daemon"), LOG_PID, LOG_DAEMON);
+/// # }
+/// ```
+#[cfg(feature = "unix")]
+pub fn openlog(ident: ConstCStr, option: c_int, facility: c_int) {
+    // Safe because `ident` is `'static`, so it outlives the logger's use of it.
+    unsafe { c_openlog(ident.as_ptr(), option, facility) }
+}
+
+impl ConstCStr {
+    /// Returns the wrapped string as an `OsStr`, without the NUL terminating byte.
+    pub fn as_os_str(&self) -> &'static OsStr {
+        OsStr::new(self.to_str())
+    }
+
+    /// Returns the wrapped string as a `Path`, without the NUL terminating byte.
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate const_cstr;
+    /// const_cstr! {
+    ///     DEV_NULL = "/dev/null";
+    /// }
+    /// # fn main() {
+    /// // The same constant also works for `libc::open(DEV_NULL.as_ptr(), libc::O_RDONLY)`.
+    /// assert!(std::fs::metadata(DEV_NULL.as_path()).is_ok());
+    /// # }
+    /// ```
+    pub fn as_path(&self) -> &'static Path {
+        Path::new(self.to_str())
+    }
+}
+
+impl AsRef<OsStr> for ConstCStr {
+    fn as_ref(&self) -> &OsStr {
+        self.as_os_str()
+    }
+}
+
+impl AsRef<Path> for ConstCStr {
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl ConstCBytes {
+    /// Returns the wrapped string as an `OsStr`, without the NUL terminating byte.
+    pub fn as_os_str(&self) -> &'static OsStr {
+        OsStr::from_bytes(self.to_bytes())
+    }
+
+    /// Returns the wrapped string as a `Path`, without the NUL terminating byte.
+    pub fn as_path(&self) -> &'static Path {
+        Path::new(self.as_os_str())
+    }
+}
+
+impl AsRef<OsStr> for ConstCBytes {
+    fn as_ref(&self) -> &OsStr {
+        self.as_os_str()
+    }
+}
+
+impl AsRef<Path> for ConstCBytes {
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+#[test]
+fn test_as_os_str_and_path() {
+    const_cstr! {
+        ROOT = "/";
+    }
+
+    const LATIN1: ConstCBytes = const_cstr!(b"/tmp/caf\xE9");
+
+    assert_eq!(ROOT.as_os_str(), "/");
+    assert!(ROOT.as_path().has_root());
+    assert!(Path::new("/tmp").starts_with(ROOT));
+    assert_eq!(LATIN1.as_os_str().as_bytes(), b"/tmp/caf\xE9");
+    assert_eq!(LATIN1.as_path().file_name(), Some(OsStr::from_bytes(b"caf\xE9")));
+}
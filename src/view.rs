@@ -0,0 +1,109 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::os::raw::c_char;
+use std::slice;
+
+use ConstCStr;
+
+/// A pointer and a length, **without** the NUL terminating byte, laid out as a C struct,
+/// for C and C++ APIs that take `string_view`-like structs by value.
+///
+/// Created from a `ConstCStr` by `ConstCStr::as_view()`, or from many at once by
+/// `CStrView::array()`:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::CStrView;
+///
+/// const_cstr_array! {
+///     const FEATURES = ["simd", "threads"];
+/// }
+///
+/// static FEATURE_VIEWS: [CStrView; 2] = CStrView::array(FEATURES);
+/// # fn main() {
+/// // e.g. `engine_enable_features(FEATURE_VIEWS.as_ptr(), FEATURE_VIEWS.len())`
+/// assert_eq!(FEATURE_VIEWS[1].len, 7);
+/// # }
+/// ```
+///
+/// The string is still followed by a NUL byte, for APIs that rely on that anyway.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct CStrView {
+    /// A pointer to the beginning of the string.
+    pub ptr: *const c_char,
+    /// The length of the string in bytes, **without** the NUL terminating byte.
+    pub len: usize,
+}
+
+// Safe because a view is only a pointer and a length, which can only be dereferenced
+// through `unsafe` code.
+unsafe impl Send for CStrView {}
+unsafe impl Sync for CStrView {}
+
+impl CStrView {
+    /// Views each of `strs`, in order.
+    pub const fn array<const N: usize>(strs: [ConstCStr; N]) -> [CStrView; N] {
+        let mut views = [CStrView { ptr: ::std::ptr::null(), len: 0 }; N];
+        let mut i = 0;
+
+        while i < N {
+            views[i] = strs[i].as_view();
+            i += 1;
+        }
+
+        views
+    }
+
+    /// Views each of `strs`, in order, collecting them into a `Vec`.
+    pub fn from_slice(strs: &[ConstCStr]) -> Vec<CStrView> {
+        strs.iter().map(ConstCStr::as_view).collect()
+    }
+
+    /// Returns `true` if the string has no bytes.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the viewed bytes.
+    ///
+    /// Safety
+    /// ------
+    /// `ptr` must point to `len` bytes, valid for reads for `'a`, as it does for a view of a
+    /// `ConstCStr`.
+    pub unsafe fn as_bytes<'a>(&self) -> &'a [u8] {
+        slice::from_raw_parts(self.ptr.cast(), self.len)
+    }
+}
+
+/// Like `ConstCStr::as_view()`.
+impl From<ConstCStr> for CStrView {
+    fn from(cstr: ConstCStr) -> CStrView {
+        cstr.as_view()
+    }
+}
+
+#[test]
+fn test_cstr_view() {
+    const_cstr_array! {
+        static NAMES = ["alpha", "", "gamma"];
+    }
+
+    const VIEW: CStrView = const_cstr!("Hello").as_view();
+
+    assert_eq!(VIEW.len, 5);
+    assert_eq!(unsafe { VIEW.as_bytes() }, b"Hello");
+
+    let views = CStrView::array(NAMES);
+    assert_eq!(views, &CStrView::from_slice(&NAMES)[..]);
+    assert_eq!(views[0], CStrView::from(NAMES[0]));
+    assert_eq!(views[2].ptr, NAMES[2].as_ptr());
+    assert!(views[1].is_empty());
+    assert_eq!(unsafe { views[2].as_bytes() }, b"gamma");
+}
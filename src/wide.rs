@@ -0,0 +1,287 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+use std::char;
+use std::fmt::{self, Write};
+
+/// A type representing a static NUL-terminated UTF-16 string, wrapping `&'static [u16]`,
+/// as taken by the `W` variants of Windows APIs.
+///
+/// Created by the `const_wstr!` macro:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstWStr;
+///
+/// const CLASS_NAME: ConstWStr = const_wstr!("MyWindowClass");
+/// # fn main() {
+/// // e.g. `CreateWindowExW(0, CLASS_NAME.as_ptr(), ...)`
+/// assert_eq!(CLASS_NAME.to_string(), "MyWindowClass");
+/// # }
+/// ```
+///
+/// Note
+/// ----
+/// Prefer the `const_wstr!` macro to create an instance of this struct
+/// over manual initialization. The macro will encode the string and include the NUL
+/// terminator for you.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConstWStr {
+    /// The wrapped UTF-16 string. Not intended to be used for manual initialization.
+    /// Public only to allow initialization by the `const_wstr!` macro.
+    ///
+    /// Includes the NUL terminator. Use `to_wide()` to get an `&'static [u16]`
+    /// without the NUL terminator.
+    pub val: &'static [u16],
+}
+
+impl ConstWStr {
+    /// Returns the wrapped string as UTF-16 code units, **without** the NUL terminator.
+    pub fn to_wide(&self) -> &'static [u16] {
+        &self.val[..self.val.len() - 1]
+    }
+
+    /// Returns the wrapped string as UTF-16 code units, **with** the NUL terminator.
+    pub fn to_wide_with_nul(&self) -> &'static [u16] {
+        self.val
+    }
+
+    /// Returns a pointer to the beginning of the wrapped string.
+    ///
+    /// Suitable for passing to any function that expects a NUL-terminated `wchar_t*` on
+    /// Windows. Since the underlying string is guaranteed to be `'static`,
+    /// the pointer should always be valid.
+    ///
+    /// Panics
+    /// ------
+    /// If the wrapped string is not NUL-terminated.
+    /// (Unlikely if you used the `const_wstr!` macro. This is just a sanity check.)
+    pub fn as_ptr(&self) -> *const u16 {
+        assert_eq!(self.val[self.val.len() - 1], 0);
+
+        self.val.as_ptr()
+    }
+}
+
+/// Decodes the wrapped string, without the NUL terminator.
+///
+/// This cannot fail, since the string was encoded from a Rust string slice.
+impl fmt::Display for ConstWStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in char::decode_utf16(self.to_wide().iter().cloned()) {
+            f.write_char(c.unwrap_or(char::REPLACEMENT_CHARACTER))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A type representing a static NUL-terminated UTF-32 string, wrapping `&'static [u32]`,
+/// as taken by the `wchar_t` APIs of platforms where `wchar_t` is 32 bits wide, such as
+/// Linux and macOS.
+///
+/// Created by the `const_wstr32!` macro:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstWStr32;
+///
+/// const GREETING: ConstWStr32 = const_wstr32!("\u{1F600} hi");
+/// # fn main() {
+/// // e.g. `libc::wprintf(GREETING.as_ptr() as *const libc::wchar_t)`
+/// assert_eq!(GREETING.to_wide()[0], 0x1F600);
+/// # }
+/// ```
+///
+/// Note
+/// ----
+/// Prefer the `const_wstr32!` macro to create an instance of this struct
+/// over manual initialization. The macro will encode the string and include the NUL
+/// terminator for you.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConstWStr32 {
+    /// The wrapped UTF-32 string. Not intended to be used for manual initialization.
+    /// Public only to allow initialization by the `const_wstr32!` macro.
+    ///
+    /// Includes the NUL terminator. Use `to_wide()` to get an `&'static [u32]`
+    /// without the NUL terminator.
+    pub val: &'static [u32],
+}
+
+impl ConstWStr32 {
+    /// Returns the wrapped string as UTF-32 code units, **without** the NUL terminator.
+    pub fn to_wide(&self) -> &'static [u32] {
+        &self.val[..self.val.len() - 1]
+    }
+
+    /// Returns the wrapped string as UTF-32 code units, **with** the NUL terminator.
+    pub fn to_wide_with_nul(&self) -> &'static [u32] {
+        self.val
+    }
+
+    /// Returns a pointer to the beginning of the wrapped string.
+    ///
+    /// Suitable for passing, after a cast to `*const wchar_t`, to any function that expects
+    /// a NUL-terminated `wchar_t*` where `wchar_t` is 32 bits wide. Since the underlying
+    /// string is guaranteed to be `'static`, the pointer should always be valid.
+    ///
+    /// Panics
+    /// ------
+    /// If the wrapped string is not NUL-terminated.
+    /// (Unlikely if you used the `const_wstr32!` macro. This is just a sanity check.)
+    pub fn as_ptr(&self) -> *const u32 {
+        assert_eq!(self.val[self.val.len() - 1], 0);
+
+        self.val.as_ptr()
+    }
+}
+
+/// Decodes the wrapped string, without the NUL terminator.
+///
+/// This cannot fail, since the string was encoded from a Rust string slice.
+impl fmt::Display for ConstWStr32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &c in self.to_wide() {
+            f.write_char(char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A type representing a static NUL-terminated UCS-2 string, wrapping `&'static [u16]`,
+/// as used throughout UEFI.
+///
+/// UCS-2 is UTF-16 without surrogate pairs, so it can only encode the characters of the
+/// Basic Multilingual Plane. Created by the `const_cstr16!` macro:
+///
+/// ```rust
+/// # #[macro_use] extern crate const_cstr;
+/// use const_cstr::ConstCStr16;
+///
+/// const BOOT_MESSAGE: ConstCStr16 = const_cstr16!("Booting\u{2026}\r\n");
+/// # fn main() {
+/// // e.g. `uefi::CStr16::from_u16_with_nul(BOOT_MESSAGE.to_wide_with_nul()).unwrap()`
+/// assert_eq!(BOOT_MESSAGE.to_wide()[7], 0x2026);
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConstCStr16 {
+    /// Ends with its only NUL terminator.
+    val: &'static [u16],
+}
+
+impl ConstCStr16 {
+    /// Wraps UCS-2 code units that already end with a NUL terminator, without checking
+    /// them.
+    ///
+    /// Safety
+    /// ------
+    /// `val` must end with a NUL code unit and must not contain any other.
+    pub const unsafe fn from_wide_with_nul_unchecked(val: &'static [u16]) -> ConstCStr16 {
+        ConstCStr16 { val }
+    }
+
+    /// Returns the wrapped string as UCS-2 code units, **without** the NUL terminator.
+    pub const fn to_wide(&self) -> &'static [u16] {
+        let (content, _) = self.val.split_at(self.val.len() - 1);
+        content
+    }
+
+    /// Returns the wrapped string as UCS-2 code units, **with** the NUL terminator, as
+    /// accepted by `uefi::CStr16::from_u16_with_nul()`.
+    pub const fn to_wide_with_nul(&self) -> &'static [u16] {
+        self.val
+    }
+
+    /// Returns a pointer to the beginning of the wrapped string.
+    ///
+    /// Suitable for passing to any UEFI protocol function that expects a `CHAR16*`. Since
+    /// the underlying string is guaranteed to be `'static`, the pointer should always be
+    /// valid.
+    pub const fn as_ptr(&self) -> *const u16 {
+        self.val.as_ptr()
+    }
+}
+
+/// Decodes the wrapped string, without the NUL terminator.
+///
+/// This cannot fail, since the string was encoded from a Rust string slice.
+impl fmt::Display for ConstCStr16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &c in self.to_wide() {
+            f.write_char(char::from_u32(c as u32).unwrap_or(char::REPLACEMENT_CHARACTER))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_creates_valid_wstr() {
+    const ASCII: ConstWStr = const_wstr!("abc");
+    const ASTRAL: &str = "\u{e9}\u{1F600}";
+
+    assert_eq!(ASCII.to_wide_with_nul(), [0x61, 0x62, 0x63, 0]);
+    assert_eq!(const_wstr!(ASTRAL).to_wide(), [0xE9, 0xD83D, 0xDE00]);
+    assert_eq!(const_wstr!(ASTRAL).to_string(), ASTRAL);
+    assert_eq!(const_wstr!("").to_wide_with_nul(), [0]);
+    assert_eq!(unsafe { *ASCII.as_ptr().offset(3) }, 0);
+}
+
+#[cfg(test)]
+mod test_creates_wstr_items_mod {
+    const_wstr! {
+        pub GREETING = "Hello";
+        /// Documented.
+        pub(crate) static FAREWELL = "Goodbye";
+    }
+}
+
+#[test]
+fn test_creates_wstr_items() {
+    use self::test_creates_wstr_items_mod::*;
+
+    assert_eq!(GREETING.to_string(), "Hello");
+    assert_eq!(FAREWELL.as_ptr(), FAREWELL.as_ptr());
+}
+
+#[test]
+fn test_creates_valid_wstr32() {
+    const ASTRAL: ConstWStr32 = const_wstr32!("a\u{e9}\u{1F600}");
+
+    assert_eq!(ASTRAL.to_wide_with_nul(), [0x61, 0xE9, 0x1F600, 0]);
+    assert_eq!(ASTRAL.to_string(), "a\u{e9}\u{1F600}");
+    assert_eq!(const_wstr32!("").to_wide(), [0u32; 0]);
+    assert_eq!(unsafe { *ASTRAL.as_ptr().offset(2) }, 0x1F600);
+}
+
+#[test]
+fn test_creates_valid_cstr16() {
+    const BMP: ConstCStr16 = const_cstr16!("a\u{e9}\u{FFFD}");
+    const WIDE: &[u16] = BMP.to_wide();
+    const UNCHECKED: ConstCStr16 = unsafe { ConstCStr16::from_wide_with_nul_unchecked(&[0x61, 0]) };
+
+    assert_eq!(BMP.to_wide_with_nul(), [0x61, 0xE9, 0xFFFD, 0]);
+    assert_eq!(WIDE, [0x61, 0xE9, 0xFFFD]);
+    assert_eq!(UNCHECKED, const_cstr16!("a"));
+    assert_eq!(BMP.to_string(), "a\u{e9}\u{FFFD}");
+    assert_eq!(const_cstr16!("").to_wide(), [0u16; 0]);
+    assert_eq!(unsafe { *BMP.as_ptr().offset(3) }, 0);
+}
+
+#[test]
+fn test_filter_wstr() {
+    const FILTER: &[u16] = const_filter_wstr!["\u{1F4C4} Text" => "*.txt", "All" => "*.*"];
+    const EMPTY: &[u16] = const_filter_wstr![];
+
+    let expected: Vec<u16> = "\u{1F4C4} Text\0*.txt\0All\0*.*\0\0".encode_utf16().collect();
+    assert_eq!(FILTER, &expected[..]);
+    assert_eq!(EMPTY, [0, 0]);
+}
+
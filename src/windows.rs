@@ -0,0 +1,73 @@
+// Copyright (c) 2015 const-cstr developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+//! Conversions to the string pointer types of `windows-sys`.
+//!
+//! `windows-sys` functions take narrow strings as `PCSTR` (`*const u8`) rather than
+//! `*const c_char`, and wide strings as `PCWSTR` (`*const u16`), so this saves an
+//! `as_ptr() as _` at every call.
+use windows_sys::core::{PCSTR, PCWSTR};
+
+use {ConstCBytes, ConstCStr, ConstWStr};
+
+impl ConstCStr {
+    /// Returns a pointer to the beginning of the wrapped string, as taken by `windows-sys`.
+    pub fn as_pcstr(&self) -> PCSTR {
+        self.as_ptr() as PCSTR
+    }
+}
+
+impl ConstCBytes {
+    /// Returns a pointer to the beginning of the wrapped string, as taken by `windows-sys`.
+    pub fn as_pcstr(&self) -> PCSTR {
+        self.as_ptr() as PCSTR
+    }
+}
+
+impl ConstWStr {
+    /// Returns a pointer to the beginning of the wrapped string, as taken by `windows-sys`.
+    ///
+    /// Panics
+    /// ------
+    /// If the wrapped string is not NUL-terminated.
+    /// (Unlikely if you used the `const_wstr!` macro. This is just a sanity check.)
+    pub fn as_pcwstr(&self) -> PCWSTR {
+        self.as_ptr()
+    }
+}
+
+impl From<ConstCStr> for PCSTR {
+    fn from(cstr: ConstCStr) -> PCSTR {
+        cstr.as_pcstr()
+    }
+}
+
+impl From<ConstCBytes> for PCSTR {
+    fn from(cbytes: ConstCBytes) -> PCSTR {
+        cbytes.as_pcstr()
+    }
+}
+
+impl From<ConstWStr> for PCWSTR {
+    fn from(wstr: ConstWStr) -> PCWSTR {
+        wstr.as_pcwstr()
+    }
+}
+
+#[test]
+fn test_windows_sys_conversions() {
+    const NARROW: ConstCStr = const_cstr!("narrow");
+    const WIDE: ConstWStr = const_wstr!("wide");
+
+    let pcstr: PCSTR = NARROW.into();
+    let pcwstr: PCWSTR = WIDE.into();
+
+    assert_eq!(pcstr, NARROW.as_ptr() as PCSTR);
+    assert_eq!(PCSTR::from(const_cstr!(b"\xFF")), const_cstr!(b"\xFF").as_pcstr());
+    assert_eq!(pcwstr, WIDE.as_ptr());
+}